@@ -2,8 +2,11 @@ use std::thread::current;
 
 use rand::{Rng, distr::slice::Empty};
 use rkglib::{graphics::{canvas::{Canvas, CanvasObject, CanvasShape, Cord}, charts::{Chart, ChartWindow}, color::ColorARGB32, drawing_primitives::{DrawOptions, Rectange}}, math::datatypes::matrix::Matrix2d};
+use rkglib::math::statistics::sampling::Sampler;
 
 const DEFAULT_SCALE: usize = 1;
+// set to Some(seed) to replay the same fires every run
+const SEED: Option<u64> = None;
 
 fn main() {
 	#[derive(Clone, Copy, PartialEq)]
@@ -30,6 +33,7 @@ fn main() {
 		pub ash_color: ColorARGB32,
 		last_time: f64,
 		current_forest: usize,
+		sampler: Sampler,
 	}
 
 	impl ForestSim {
@@ -50,11 +54,12 @@ fn main() {
 				ash_color: ColorARGB32(0xFF505050),
 				last_time: 0.0,
 				current_forest: 0,
+				sampler: SEED.map_or_else(Sampler::from_os_rng, Sampler::new),
 			}
 		}
 
 		fn update(&mut self, t: f64) {
-			let mut rng = rand::rng();
+			let rng = self.sampler.rng();
 			self.last_time = t;
 
 			let c = self.current_forest;
@@ -80,7 +85,7 @@ fn main() {
 					// spread fire
 					CellState::Burning(burn_t) => {
 						if burn_t <= t {
-							self.forest[n].data[i] = CellState::Ash(self.ash_length+(t* rng.random_range(0.1..1.0) as f64));
+							self.forest[n].data[i] = CellState::Ash(self.ash_length+(t* rng.random_range(0.1..1.0)));
 						}
 						else {
 							if rng.random_range(0.0..1.0) < self.spread_chance {
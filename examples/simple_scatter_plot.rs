@@ -17,6 +17,7 @@ fn main() {
 	let mut scatter = ScatterPlot::from_table(
 		RkgTabN::from_flat(&data, &[50,2])
 	);
+	scatter.fit_trend_line();
 	scatter.pos = Cord{x: 50, y: 50};
 	scatter.canvas.set_shape(CanvasShape{width: 200, height: 200, depth: 4});
 	chart_window.charts.push(Box::new(scatter));
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use num_traits::NumCast;
@@ -9,6 +10,7 @@ use crate::graphics::canvas::CanvasShape;
 use crate::graphics::canvas::Cord;
 use crate::graphics::color::ColorARGB32;
 use crate::math::datatypes::rkgtab::RkgTabN;
+use crate::math::statistics::regression::{Regression, linear_regression};
 
 /// A 2d scatter plot chart compatible with the ChartWindow
 pub struct ScatterPlot<T: Clone + Copy + Display> {
@@ -31,7 +33,10 @@ pub struct ScatterPlot<T: Clone + Copy + Display> {
 	/// show the key
 	pub show_key: bool,
 	/// dot radius px
-	pub point_radius: usize
+	pub point_radius: usize,
+	/// fit of the y axis collumn against the x axis collumn drawn over the points
+	/// only fits with a single x are drawn, see fit_trend_line
+	pub trend_line: Option<Regression>
 }
 
 impl<T: Copy+Clone+Display> ScatterPlot<T> {
@@ -48,10 +53,46 @@ impl<T: Copy+Clone+Display> ScatterPlot<T> {
 			classifications: Vec::new(),
 			show_key: true,
 			point_radius: 5,
+			trend_line: None,
 		}
 	}
 }
 
+impl<T: Copy+Clone+Display+Into<f64>> ScatterPlot<T> {
+	/// sets trend_line to the linear regression of the y axis collumn against
+	/// the x axis collumn of data_table and returns it
+	/// None if the collumns can not be fit, like when every x is the same
+	pub fn fit_trend_line(&mut self) -> Option<&Regression> {
+		self.trend_line = linear_regression(&self.data_table, self.x_axis, self.y_axis);
+		self.trend_line.as_ref()
+	}
+}
+
+
+impl<T: Copy+Clone+Display> ScatterPlot<T> {
+	// joins the predicted y of each pixel collumn to the next with a vertical
+	// run so steep fits stay connected, parts off the canvas are skipped
+	fn draw_trend_line(canvas: &mut Canvas, trend_line: &Regression) {
+		let top = canvas.height() as i32 - 1;
+		let predict = |x: i32| trend_line.predict(&[x as f64]).map(|y| y.round().clamp(-1.0, top as f64 + 1.0) as i32);
+
+		let mut previous = match predict(0) { Some(y) => y, None => return };
+		for x in 0..canvas.width() as i32 {
+			let Some(y) = predict(x) else { return };
+			// the run starts past the row the last collumn ended on so a
+			// diagonal stays one pixel wide
+			let (low, high) = match y.cmp(&previous) {
+				Ordering::Greater => (previous + 1, y),
+				Ordering::Less => (y, previous - 1),
+				Ordering::Equal => (y, y),
+			};
+			for run_y in low.max(0)..=high.min(top) {
+				canvas.paint(&Cord { x, y: run_y }, ColorARGB32(0xFFFF0000));
+			}
+			previous = y;
+		}
+	}
+}
 
 impl<T: NumCast+Clone+Copy+Display> Chart for ScatterPlot<T> {
 	fn draw(&mut self) -> &Canvas {
@@ -74,6 +115,10 @@ impl<T: NumCast+Clone+Copy+Display> Chart for ScatterPlot<T> {
 			
 
 		}
+
+		if let Some(trend_line) = &self.trend_line {
+			Self::draw_trend_line(&mut self.canvas, trend_line);
+		}
 		&self.canvas
 	}
 
@@ -101,4 +146,99 @@ impl<T: NumCast+Clone+Copy+Display> Chart for ScatterPlot<T> {
 	fn y(&self) -> i32 {
 		self.pos.y
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::graphics::canvas::CanvasShape;
+	use crate::math::statistics::regression::multiple_regression;
+
+	const RED: u32 = 0xFFFF0000;
+
+	// a chart of (x, y) rows on a 10 by 10 canvas
+	fn chart(rows: &[(i32, i32)]) -> ScatterPlot<i32> {
+		let data: Vec<i32> = rows.iter().flat_map(|(x, y)| [*x, *y]).collect();
+		let mut chart = ScatterPlot::from_table(RkgTabN::from_flat(&data, &[rows.len(), 2]));
+		chart.canvas.set_shape(CanvasShape { width: 10, height: 10, depth: 4 });
+		chart
+	}
+
+	// the painted pixels of the trend line, by collumn then row
+	fn red_pixels(canvas: &Canvas) -> Vec<(i32, i32)> {
+		let mut ret_val = Vec::new();
+		for x in 0..canvas.width() as i32 {
+			for y in 0..canvas.height() as i32 {
+				if canvas.get(&Cord { x, y }).0 == RED { ret_val.push((x, y)); }
+			}
+		}
+		ret_val
+	}
+
+	// region: test_trend_line
+	#[test] fn test_fit_trend_line() {
+		let mut chart = chart(&[(0, 1), (2, 5), (4, 9)]);
+		let fit = chart.fit_trend_line().unwrap();
+		assert!((fit.coefficients[0] - 1.0).abs() < 1e-12);
+		assert!((fit.coefficients[1] - 2.0).abs() < 1e-12);
+		assert!(chart.trend_line.is_some());
+
+		// the axes pick the collumns, so swapping them fits x against y
+		chart.x_axis = 1;
+		chart.y_axis = 0;
+		assert!((chart.fit_trend_line().unwrap().coefficients[1] - 0.5).abs() < 1e-12);
+
+		let mut vertical = self::chart(&[(3, 1), (3, 5)]);
+		assert!(vertical.fit_trend_line().is_none());
+		assert!(vertical.trend_line.is_none());
+	}
+
+	#[test] fn test_draw_trend_line() {
+		let mut chart = chart(&[(1, 1), (8, 8)]);
+		chart.fit_trend_line();
+		let canvas = chart.draw();
+		assert_eq!(red_pixels(canvas), (0..10).map(|i| (i, i)).collect::<Vec<_>>());
+
+		let mut falling = self::chart(&[(0, 9), (9, 0)]);
+		falling.fit_trend_line();
+		assert_eq!(red_pixels(falling.draw()), (0..10).map(|i| (i, 9 - i)).collect::<Vec<_>>());
+	}
+
+	#[test] fn test_draw_steep_trend_line() {
+		// y = 3x climbs 3 rows a collumn, the rows between are filled in
+		// until it leaves through the top of the canvas
+		let mut chart = chart(&[(0, 0), (1, 3), (2, 6)]);
+		chart.fit_trend_line();
+		let expected: Vec<(i32, i32)> = [(0, 0..=0), (1, 1..=3), (2, 4..=6), (3, 7..=9)]
+			.into_iter()
+			.flat_map(|(x, rows)| rows.map(move |y| (x, y)))
+			.collect();
+		assert_eq!(red_pixels(chart.draw()), expected);
+	}
+
+	#[test] fn test_draw_trend_line_off_canvas() {
+		let mut chart = chart(&[(0, 0), (1, 0)]);
+		let mut above = self::chart(&[(0, 100), (1, 101)]);
+		chart.trend_line = above.fit_trend_line().cloned();
+		assert!(red_pixels(chart.draw()).is_empty());
+
+		// a line crossing the canvas is only painted where it is on it
+		let mut below = self::chart(&[(0, -20), (1, -15)]);
+		chart.trend_line = below.fit_trend_line().cloned();
+		let expected: Vec<(i32, i32)> = [(4, 0..=0), (5, 1..=5), (6, 6..=9)]
+			.into_iter()
+			.flat_map(|(x, rows)| rows.map(move |y| (x, y)))
+			.collect();
+		assert_eq!(red_pixels(chart.draw()), expected);
+	}
+
+	#[test] fn test_draw_trend_line_with_two_x() {
+		// a fit of two x collumns can not be predicted from one x
+		let table = RkgTabN::from_flat(&[0, 1, 1, 1, 0, 2, 1, 1, 4, 2, 2, 5], &[4, 3]);
+		let mut chart = chart(&[(0, 0), (1, 1)]);
+		chart.trend_line = multiple_regression(&table, &[0, 1], 2);
+		assert!(chart.trend_line.is_some());
+		assert!(red_pixels(chart.draw()).is_empty());
+	}
+	// endregion:
+}
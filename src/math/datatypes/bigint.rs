@@ -0,0 +1,536 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+// MARK: BigInt
+
+/// An arbitrary precision signed integer.
+///
+/// The magnitude is stored as base 2^32 digits with the least significant
+/// digit first.  Zero is stored as an empty magnitude and is never negative,
+/// so two equal values always have the same representation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+	negative: bool,
+	magnitude: Vec<u32>,
+}
+
+/// Error returned when a string can not be parsed as a BigInt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl fmt::Display for ParseBigIntError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "invalid digit found in string")
+	}
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+impl BigInt {
+	/// Returns a BigInt equal to zero
+	pub fn zero() -> Self {
+		Self { negative: false, magnitude: Vec::new() }
+	}
+
+	/// Returns a BigInt equal to one
+	pub fn one() -> Self {
+		Self::from(1u32)
+	}
+
+	/// Returns true if the value is zero
+	pub fn is_zero(&self) -> bool {
+		self.magnitude.is_empty()
+	}
+
+	/// Returns true if the value is less than zero
+	pub fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	/// Returns -1, 0 or 1 depending on the sign of the value
+	pub fn signum(&self) -> i32 {
+		if self.is_zero() { 0 }
+		else if self.negative { -1 }
+		else { 1 }
+	}
+
+	/// Returns the number of bits in the absolute value, zero for zero
+	pub fn bits(&self) -> u64 {
+		match self.magnitude.last() {
+			None => 0,
+			Some(top) => self.magnitude.len() as u64 * 32 - top.leading_zeros() as u64,
+		}
+	}
+
+	/// Returns the absolute value
+	pub fn abs(&self) -> Self {
+		Self { negative: false, magnitude: self.magnitude.clone() }
+	}
+
+	/// Raises the value to the power of exp
+	pub fn pow(&self, exp: u32) -> Self {
+		let mut ret_val = Self::one();
+		let mut base = self.clone();
+		let mut exp = exp;
+
+		while exp > 0 {
+			if exp & 1 == 1 { ret_val = &ret_val * &base; }
+			base = &base * &base;
+			exp >>= 1;
+		}
+
+		ret_val
+	}
+
+	/// Greatest common divisor of two values, always positive unless both are zero
+	pub fn gcd(a: &Self, b: &Self) -> Self {
+		let mut a = a.abs();
+		let mut b = b.abs();
+		while !b.is_zero() {
+			let r = &a % &b;
+			a = b;
+			b = r;
+		}
+		a
+	}
+
+	/// Returns quotient and remainder, truncating towards zero like the primitive integers
+	/// panics if divisor is zero
+	pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+		if divisor.is_zero() { panic!("attempt to divide by zero"); }
+
+		let (q, r) = div_rem_magnitude(&self.magnitude, &divisor.magnitude);
+		(
+			Self::from_parts(self.negative != divisor.negative, q),
+			Self::from_parts(self.negative, r)
+		)
+	}
+
+	/// Returns the value as an i128 or None if it does not fit
+	pub fn to_i128(&self) -> Option<i128> {
+		let magnitude = self.to_u128_magnitude()?;
+		if self.negative {
+			if magnitude == i128::MIN.unsigned_abs() { Some(i128::MIN) }
+			else { i128::try_from(magnitude).ok().map(|v| -v) }
+		}
+		else { i128::try_from(magnitude).ok() }
+	}
+
+	/// Returns the value as a u128 or None if it does not fit
+	pub fn to_u128(&self) -> Option<u128> {
+		if self.negative { return None }
+		self.to_u128_magnitude()
+	}
+
+	/// Returns the closest f64 to the value, or infinity if it is too large
+	pub fn to_f64(&self) -> f64 {
+		let mut ret_val = 0.0;
+		for digit in self.magnitude.iter().rev() {
+			ret_val = ret_val * 4294967296.0 + *digit as f64;
+		}
+		if self.negative { -ret_val } else { ret_val }
+	}
+
+	// builds a value from a sign and an unnormalized magnitude
+	fn from_parts(negative: bool, magnitude: Vec<u32>) -> Self {
+		let mut ret_val = Self { negative, magnitude };
+		ret_val.normalize();
+		ret_val
+	}
+
+	// removes leading zero digits and the sign of zero
+	fn normalize(&mut self) {
+		while self.magnitude.last() == Some(&0) {
+			self.magnitude.pop();
+		}
+		if self.magnitude.is_empty() { self.negative = false; }
+	}
+
+	fn to_u128_magnitude(&self) -> Option<u128> {
+		if self.magnitude.len() > 4 { return None }
+		let mut ret_val: u128 = 0;
+		for digit in self.magnitude.iter().rev() {
+			ret_val = (ret_val << 32) | *digit as u128;
+		}
+		Some(ret_val)
+	}
+}
+
+// MARK: Magnitude helpers
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+	if a.len() != b.len() { return a.len().cmp(&b.len()) }
+	for i in (0..a.len()).rev() {
+		match a[i].cmp(&b[i]) {
+			Ordering::Equal => continue,
+			other => return other,
+		}
+	}
+	Ordering::Equal
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+	let mut ret_val = Vec::with_capacity(a.len().max(b.len()) + 1);
+	let mut carry = 0u64;
+	for i in 0..a.len().max(b.len()) {
+		let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+		ret_val.push(sum as u32);
+		carry = sum >> 32;
+	}
+	if carry > 0 { ret_val.push(carry as u32); }
+	ret_val
+}
+
+// a must be greater than or equal to b
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+	let mut ret_val = Vec::with_capacity(a.len());
+	let mut borrow = 0i64;
+	for (i, digit) in a.iter().enumerate() {
+		let mut diff = *digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+		if diff < 0 {
+			diff += 1 << 32;
+			borrow = 1;
+		}
+		else { borrow = 0; }
+		ret_val.push(diff as u32);
+	}
+	ret_val
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+	if a.is_empty() || b.is_empty() { return Vec::new() }
+
+	let mut ret_val = vec![0u32; a.len() + b.len()];
+	for (i, x) in a.iter().enumerate() {
+		let mut carry = 0u64;
+		for (j, y) in b.iter().enumerate() {
+			let product = *x as u64 * *y as u64 + ret_val[i + j] as u64 + carry;
+			ret_val[i + j] = product as u32;
+			carry = product >> 32;
+		}
+		ret_val[i + b.len()] = carry as u32;
+	}
+	ret_val
+}
+
+// long division one bit at a time, returns (quotient, remainder)
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+	if cmp_magnitude(a, b) == Ordering::Less { return (Vec::new(), a.to_vec()) }
+
+	if b.len() == 1 {
+		let divisor = b[0] as u64;
+		let mut quotient = vec![0u32; a.len()];
+		let mut rem = 0u64;
+		for i in (0..a.len()).rev() {
+			let current = (rem << 32) | a[i] as u64;
+			quotient[i] = (current / divisor) as u32;
+			rem = current % divisor;
+		}
+		return (quotient, vec![rem as u32])
+	}
+
+	let mut quotient = vec![0u32; a.len()];
+	let mut rem: Vec<u32> = Vec::new();
+	for i in (0..a.len() * 32).rev() {
+		// rem = rem * 2 + next bit of a
+		let mut carry = (a[i / 32] >> (i % 32)) & 1;
+		for digit in rem.iter_mut() {
+			let next = *digit >> 31;
+			*digit = (*digit << 1) | carry;
+			carry = next;
+		}
+		if carry > 0 { rem.push(carry); }
+
+		if cmp_magnitude(&rem, b) != Ordering::Less {
+			rem = sub_magnitude(&rem, b);
+			while rem.last() == Some(&0) { rem.pop(); }
+			quotient[i / 32] |= 1 << (i % 32);
+		}
+	}
+	(quotient, rem)
+}
+
+// MARK: Conversions
+
+macro_rules! from_unsigned {
+	($($t:ty),*) => { $(
+		impl From<$t> for BigInt {
+			fn from(value: $t) -> Self {
+				let mut value = value as u128;
+				let mut magnitude = Vec::new();
+				while value > 0 {
+					magnitude.push(value as u32);
+					value >>= 32;
+				}
+				Self { negative: false, magnitude }
+			}
+		}
+	)* };
+}
+
+macro_rules! from_signed {
+	($($t:ty),*) => { $(
+		impl From<$t> for BigInt {
+			fn from(value: $t) -> Self {
+				let mut ret_val = Self::from((value as i128).unsigned_abs());
+				ret_val.negative = value < 0;
+				ret_val
+			}
+		}
+	)* };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl FromStr for BigInt {
+	type Err = ParseBigIntError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (negative, digits) = match s.strip_prefix('-') {
+			Some(rest) => (true, rest),
+			None => (false, s.strip_prefix('+').unwrap_or(s)),
+		};
+
+		if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+			return Err(ParseBigIntError)
+		}
+
+		let ten = Self::from(10u32);
+		let mut ret_val = Self::zero();
+		for b in digits.bytes() {
+			ret_val = &(&ret_val * &ten) + &Self::from(b - b'0');
+		}
+
+		if negative { ret_val = -ret_val; }
+		Ok(ret_val)
+	}
+}
+
+impl fmt::Display for BigInt {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_zero() { return f.pad_integral(true, "", "0") }
+
+		// peel off base 10^9 chunks from the bottom
+		let mut chunks = Vec::new();
+		let mut rest = self.magnitude.clone();
+		while !rest.is_empty() {
+			let (q, r) = div_rem_magnitude(&rest, &[1_000_000_000]);
+			chunks.push(r.first().copied().unwrap_or(0));
+			rest = q;
+			while rest.last() == Some(&0) { rest.pop(); }
+		}
+
+		let mut text = chunks.pop().unwrap_or(0).to_string();
+		for chunk in chunks.iter().rev() {
+			text.push_str(&format!("{:09}", chunk));
+		}
+
+		f.pad_integral(!self.negative, "", &text)
+	}
+}
+
+// MARK: Ordering
+
+impl PartialOrd for BigInt {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for BigInt {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self.negative, other.negative) {
+			(false, true) => Ordering::Greater,
+			(true, false) => Ordering::Less,
+			(false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+			(true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+		}
+	}
+}
+
+// MARK: Operators
+
+impl Neg for &BigInt {
+	type Output = BigInt;
+
+	fn neg(self) -> Self::Output {
+		BigInt::from_parts(!self.negative, self.magnitude.clone())
+	}
+}
+
+impl Neg for BigInt {
+	type Output = BigInt;
+
+	fn neg(self) -> Self::Output {
+		BigInt::from_parts(!self.negative, self.magnitude)
+	}
+}
+
+impl Add for &BigInt {
+	type Output = BigInt;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		if self.negative == rhs.negative {
+			return BigInt::from_parts(self.negative, add_magnitude(&self.magnitude, &rhs.magnitude))
+		}
+
+		match cmp_magnitude(&self.magnitude, &rhs.magnitude) {
+			Ordering::Less => BigInt::from_parts(rhs.negative, sub_magnitude(&rhs.magnitude, &self.magnitude)),
+			_ => BigInt::from_parts(self.negative, sub_magnitude(&self.magnitude, &rhs.magnitude)),
+		}
+	}
+}
+
+impl Sub for &BigInt {
+	type Output = BigInt;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + &(-rhs)
+	}
+}
+
+impl Mul for &BigInt {
+	type Output = BigInt;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		BigInt::from_parts(self.negative != rhs.negative, mul_magnitude(&self.magnitude, &rhs.magnitude))
+	}
+}
+
+impl Div for &BigInt {
+	type Output = BigInt;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self.div_rem(rhs).0
+	}
+}
+
+impl Rem for &BigInt {
+	type Output = BigInt;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		self.div_rem(rhs).1
+	}
+}
+
+// forwards the owned versions of the operators to the borrowed ones
+macro_rules! forward_owned_op {
+	($op:ident, $method:ident) => {
+		impl $op for BigInt {
+			type Output = BigInt;
+
+			fn $method(self, rhs: Self) -> Self::Output {
+				(&self).$method(&rhs)
+			}
+		}
+	};
+}
+
+forward_owned_op!(Add, add);
+forward_owned_op!(Sub, sub);
+forward_owned_op!(Mul, mul);
+forward_owned_op!(Div, div);
+forward_owned_op!(Rem, rem);
+
+// MARK: Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn big(s: &str) -> BigInt {
+		s.parse().unwrap()
+	}
+
+// region: test_parse_display
+	fn test_parse_display(text: &str, expected: &str) {
+		assert_eq!(big(text).to_string(), expected);
+	}
+
+	#[test] fn test_parse_display_0() { test_parse_display("0", "0"); }
+	#[test] fn test_parse_display_1() { test_parse_display("-0", "0"); }
+	#[test] fn test_parse_display_2() { test_parse_display("+42", "42"); }
+	#[test] fn test_parse_display_3() { test_parse_display("-1000000000", "-1000000000"); }
+	#[test] fn test_parse_display_4() { test_parse_display(
+		"815915283247897734345611269596115894272000000000",
+		"815915283247897734345611269596115894272000000000"
+	);}
+	#[test] fn test_parse_display_5() { test_parse_display("000123", "123"); }
+
+	#[test] fn test_parse_errors() {
+		assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError));
+		assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError));
+		assert_eq!("12a".parse::<BigInt>(), Err(ParseBigIntError));
+	}
+// endregion:
+
+// region: test_arithmetic
+	fn test_arithmetic(a: i128, b: i128) {
+		let (x, y) = (BigInt::from(a), BigInt::from(b));
+		assert_eq!((&x + &y).to_i128(), Some(a + b));
+		assert_eq!((&x - &y).to_i128(), Some(a - b));
+		assert_eq!((&x * &y).to_i128(), Some(a * b));
+		if b != 0 {
+			assert_eq!((&x / &y).to_i128(), Some(a / b));
+			assert_eq!((&x % &y).to_i128(), Some(a % b));
+		}
+		assert_eq!(x.cmp(&y), a.cmp(&b));
+	}
+
+	#[test] fn test_arithmetic_0() { test_arithmetic(0, 0); }
+	#[test] fn test_arithmetic_1() { test_arithmetic(17, 5); }
+	#[test] fn test_arithmetic_2() { test_arithmetic(-17, 5); }
+	#[test] fn test_arithmetic_3() { test_arithmetic(17, -5); }
+	#[test] fn test_arithmetic_4() { test_arithmetic(-4294967296, 4294967295); }
+	#[test] fn test_arithmetic_5() { test_arithmetic(9_223_372_036_854_775_807, -3_037_000_499); }
+	#[test] fn test_arithmetic_6() { test_arithmetic(123_456_789_012_345_678_901, 98_765_432_109); }
+
+	#[test] fn test_large_division() {
+		let a = big("90548514656103281165404177077484163874504589675413336841320");
+		let b = big("137846528820");
+		let (q, r) = a.div_rem(&b);
+		assert_eq!(&(&q * &b) + &r, a);
+		assert!(r < b);
+	}
+
+	#[test] fn test_pow() {
+		assert_eq!(BigInt::from(2).pow(128).to_string(), "340282366920938463463374607431768211456");
+		assert_eq!(BigInt::from(-3).pow(3), BigInt::from(-27));
+		assert_eq!(BigInt::from(7).pow(0), BigInt::one());
+	}
+
+	#[test] fn test_gcd() {
+		assert_eq!(BigInt::gcd(&BigInt::from(-12), &BigInt::from(18)), BigInt::from(6));
+		assert_eq!(BigInt::gcd(&BigInt::from(0), &BigInt::from(5)), BigInt::from(5));
+	}
+// endregion:
+
+// region: test_conversions
+	#[test] fn test_to_i128() {
+		assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+		assert_eq!(BigInt::from(i128::MAX).to_i128(), Some(i128::MAX));
+		assert_eq!((BigInt::from(i128::MAX) + BigInt::one()).to_i128(), None);
+	}
+
+	#[test] fn test_to_u128() {
+		assert_eq!(BigInt::from(u128::MAX).to_u128(), Some(u128::MAX));
+		assert_eq!(BigInt::from(-1).to_u128(), None);
+		assert_eq!((BigInt::from(u128::MAX) + BigInt::one()).to_u128(), None);
+	}
+
+	#[test] fn test_to_f64() {
+		assert_eq!(BigInt::from(-1_000_000_000_000i64).to_f64(), -1e12);
+		assert_eq!(BigInt::from(2).pow(100).to_f64(), 2f64.powi(100));
+	}
+
+	#[test] fn test_bits() {
+		assert_eq!(BigInt::zero().bits(), 0);
+		assert_eq!(BigInt::from(-5).bits(), 3);
+		assert_eq!(BigInt::from(u32::MAX).bits(), 32);
+		assert_eq!(BigInt::from(2).pow(100).bits(), 101);
+	}
+// endregion:
+
+}
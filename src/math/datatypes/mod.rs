@@ -1,6 +1,9 @@
 /// Contains the struc rational which models a rational number
 pub mod rational;
 
+/// Contains the struct BigInt which models an arbitrary precision integer
+pub mod bigint;
+
 /// Contains the struc set which models a set theroy set
 pub mod set;
 
@@ -1,13 +1,27 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::math::datatypes::bigint::BigInt;
+use crate::math::factorization::PrimeFactorization;
+
 // MARK: Rational
 
 /// This is a repensentation of a rational number.
 /// 
 /// This is implemented as a list of numbers that are multiplied together for
 /// the numerator and a list of numbers that are multiplied together for
-/// the denominator.
+/// the denominator.  An empty list has the value of one.
+///
+/// Every arithmetic operation leaves the value in lowest terms as sorted lists
+/// of primes, with the sign carried by a leading `-1` factor in the numerator.
+/// Factors are big integers, so a part with no prime below 2^16 that is too
+/// large to split is kept whole rather than overflowing.
+#[derive(Clone, Debug)]
 pub struct Rational {
-	nums: Vec<i32>,
-	denominators: Vec<i32>,
+	nums: Vec<BigInt>,
+	denominators: Vec<BigInt>,
 }
 
 impl Rational {
@@ -20,92 +34,567 @@ impl Rational {
 
 	/// Inits a new DecompsedNum with two arrays
 	pub fn new_from_arrays(nums: &[i32], denoms: &[i32]) -> Self {
-		Self {nums: nums.to_vec(), denominators: denoms.to_vec() }
+		let to_factors = |factors: &[i32]| factors.iter().map(|f| BigInt::from(*f)).collect();
+		Self {nums: to_factors(nums), denominators: to_factors(denoms) }
+	}
+
+	/// Inits a new Rational from a numerator and denominator and reduces it
+	/// panics if the denominator is zero
+	pub fn new_from_fraction(num: i32, denom: i32) -> Self {
+		let mut ret_val = Self::new_from_arrays(&[num], &[denom]);
+		ret_val.cancel_out();
+		ret_val
 	}
 
-	/// Inits a new Rational with a factorial squence
+	/// Inits a new Rational with the prime factors of n factorial
 	pub fn factorial(n: u32) -> Self {
-		let mut ret_val = Self::new();
+		Self::from(&PrimeFactorization::factorial(n))
+	}
 
-		for i in 1..n+1 {
-			ret_val.nums.push(i as i32);
-		}
+	/// Returns the prime factorization of the absolute value
+	/// None if the value is zero or has a factor above u32::MAX with no prime below 2^16
+	/// panics if any denominator factor is zero
+	pub fn prime_factorization(&self) -> Option<PrimeFactorization> {
+		if self.denominators.iter().any(BigInt::is_zero) { panic!("attempt to divide by zero"); }
+		if self.is_zero() { return None }
 
-		if n == 0 {
-			ret_val.nums.push(1);
+		let mut factors = PrimeFactorization::new();
+		for f in self.nums.iter() {
+			factors.multiply(&factor_primes(f)?);
+		}
+		for f in self.denominators.iter() {
+			factors.divide(&factor_primes(f)?);
 		}
 
-		ret_val
+		Some(factors)
 	}
 
 	/// Attempts to multiply and divide are numbers to return the composed number
+	/// panics if the result does not fit in an i128, see evaluate_checked
 	pub fn evaluate(&self) -> i128 {
-		//TODO return none if overflow
-		let mut ret_val: i128 = 1;
+		self.evaluate_checked().expect("Rational evaluation overflowed an i128")
+	}
 
-		if self.nums.len() == 0 { ret_val = 0 }
-		for i in self.nums.iter() {
-			ret_val *= *i as i128;
-		}
+	/// Multiplies and divides the numbers to return the composed number
+	/// returns None if the result does not fit in an i128
+	pub fn evaluate_checked(&self) -> Option<i128> {
+		self.evaluate_big().to_i128()
+	}
 
-		for i in self.denominators.iter() {
-			ret_val /= *i as i128;
-		}
+	/// Multiplies and divides the numbers to return the exact composed number
+	/// as an arbitrary precision integer, it can not overflow.
+	/// Empty factor lists count as one and any fraction is truncated towards zero
+	pub fn evaluate_big(&self) -> BigInt {
+		&self.numerator_big() / &self.denominator_big()
+	}
+
+	/// Returns the product of the numerator factors, carrying the sign of the value
+	/// panics if the product does not fit in an i128
+	pub fn numerator(&self) -> i128 {
+		self.numerator_big().to_i128().expect("Rational numerator overflowed an i128")
+	}
+
+	/// Returns the product of the denominator factors, always positive
+	/// panics if the product does not fit in an i128
+	pub fn denominator(&self) -> i128 {
+		self.denominator_big().to_i128().expect("Rational denominator overflowed an i128")
+	}
+
+	/// Returns the exact product of the numerator factors, carrying the sign of the value
+	pub fn numerator_big(&self) -> BigInt {
+		let (num, denom) = self.products();
+		if denom.is_negative() { -num } else { num }
+	}
+
+	/// Returns the exact product of the denominator factors, always positive
+	pub fn denominator_big(&self) -> BigInt {
+		self.products().1.abs()
+	}
+
+	/// Returns -1, 0 or 1 depending on the sign of the value
+	pub fn signum(&self) -> i32 {
+		if self.is_zero() { return 0 }
+
+		let negatives = self.nums.iter()
+			.chain(self.denominators.iter())
+			.filter(|f| f.is_negative())
+			.count();
+
+		if negatives % 2 == 0 { 1 } else { -1 }
+	}
 
+	/// Returns true if the value is zero
+	pub fn is_zero(&self) -> bool {
+		self.nums.iter().any(BigInt::is_zero)
+	}
+
+	/// Returns the absolute value
+	pub fn abs(&self) -> Self {
+		if self.signum() < 0 { -self } else { self.clone() }
+	}
+
+	/// Returns one divided by this value
+	/// panics if the value is zero
+	pub fn recip(&self) -> Self {
+		let mut ret_val = Self { nums: self.denominators.clone(), denominators: self.nums.clone() };
+		ret_val.cancel_out();
 		ret_val
 	}
 
-	/// Removes any pairs from the numerator and denominator
+	/// Returns the closest f64 to the value
+	/// panics if any denominator factor is zero
+	pub fn to_f64(&self) -> f64 {
+		let (num, denom) = self.products();
+		if denom.is_zero() { panic!("attempt to divide by zero"); }
+		if num.is_zero() { return 0.0 }
+
+		// scale the products so the quotient has 64 bits, the products can be
+		// far outside the range of an f64 even when their ratio is not
+		let shift = 64 + denom.bits() as i64 - num.bits() as i64;
+		let two = BigInt::from(2);
+		let (scaled_num, scaled_denom) = if shift >= 0 {
+			(&num.abs() * &two.pow(shift as u32), denom.abs())
+		}
+		else {
+			(num.abs(), &denom.abs() * &two.pow(-shift as u32))
+		};
+
+		// a remainder is kept as a low bit so the quotient rounds the right way
+		let (q, r) = scaled_num.div_rem(&scaled_denom);
+		let q = if r.is_zero() { &q * &two } else { &(&q * &two) + &BigInt::one() };
+
+		// undo the scaling in two steps so a subnormal result is not flushed to zero
+		let exp = -(shift + 1);
+		let half = exp / 2;
+		let ret_val = q.to_f64() * 2f64.powi(half as i32) * 2f64.powi((exp - half) as i32);
+
+		if num.is_negative() != denom.is_negative() { -ret_val } else { ret_val }
+	}
+
+	/// Reduces the value to lowest terms.
+	///
+	/// Every factor is split into its primes below 2^16 and the exponents of
+	/// each prime are cancelled.  The larger parts left over are cancelled by
+	/// their common divisors, leaving sorted factor lists.  The sign is moved
+	/// into a single `-1` factor at the front of the numerator.
+	/// panics if any denominator factor is zero
 	pub fn cancel_out(&mut self) {
-		let mut i = 0;
-		while i < self.denominators.len() {
-			let mut found_pair = false;
-			for j in 0..self.nums.len() {
-				if self.nums[j] == self.denominators[i] {
-					self.nums.remove(j);
-					self.denominators.remove(i);
-					found_pair = true;
-					break;
-				}
-			}
-			if !found_pair { i += 1 };
+		if self.denominators.iter().any(BigInt::is_zero) { panic!("attempt to divide by zero"); }
+		if self.is_zero() {
+			self.nums = vec![BigInt::zero()];
+			self.denominators.clear();
+			return
 		}
+
+		let negative = self.signum() < 0;
+		let mut primes = PrimeFactorization::new();
+		let mut nums = Vec::new();
+		let mut denominators = Vec::new();
+
+		for f in self.nums.iter() {
+			let (small, rest) = PrimeFactorization::split_small_primes(f);
+			primes.multiply(&small);
+			if rest != BigInt::one() { nums.push(rest); }
+		}
+		for f in self.denominators.iter() {
+			let (small, rest) = PrimeFactorization::split_small_primes(f);
+			primes.divide(&small);
+			if rest != BigInt::one() { denominators.push(rest); }
+		}
+		cancel_common_divisors(&mut nums, &mut denominators);
+
+		*self = Self::from(&primes);
+		self.nums.extend(nums.into_iter().filter(|f| *f != BigInt::one()));
+		self.denominators.extend(denominators.into_iter().filter(|f| *f != BigInt::one()));
+		self.nums.sort();
+		self.denominators.sort();
+		if negative { self.nums.insert(0, BigInt::from(-1)); }
 	}
 
 	/// Multiplies and cancels out two rational numbers
 	pub fn multiply(&mut self, other: &Self) {
 		for i in other.nums.iter() {
-			self.nums.push(*i);
+			self.nums.push(i.clone());
 		}
 		for i in other.denominators.iter() {
-			self.denominators.push(*i);
+			self.denominators.push(i.clone());
 		}
 		self.cancel_out();
-		self.nums.sort();
-		self.denominators.sort();
 	}
 
 	/// Takes anouther Rational and divides it into this one
 	pub fn divide(&mut self, other: &Self) {
 		for i in other.nums.iter() {
-			self.denominators.push(*i);
+			self.denominators.push(i.clone());
 		}
 		for i in other.denominators.iter() {
-			self.nums.push(*i);
+			self.nums.push(i.clone());
 		}
 		self.cancel_out();
-		self.nums.sort();
-		self.denominators.sort();
 	}
 
+	// multiplies out both factor lists
+	fn products(&self) -> (BigInt, BigInt) {
+		let product = |factors: &[BigInt]| factors.iter()
+			.fold(BigInt::one(), |acc, f| &acc * f);
+
+		(product(&self.nums), product(&self.denominators))
+	}
+
+	/// Finds the closest Rational to x whose denominator is at most max_denominator.
+	///
+	/// Walks the continued fraction of x and checks the last semiconvergent, so
+	/// the result is the best rational approximation for that denominator bound.
+	/// returns None if x is not finite or not below 2^64, or if max_denominator is zero
+	pub fn from_f64_approx(x: f64, max_denominator: u32) -> Option<Self> {
+		if !x.is_finite() || x.abs() >= 2f64.powi(64) || max_denominator == 0 { return None }
+
+		let max = max_denominator as u128;
+		let target = x.abs();
+		let mut rest = target;
+
+		// (p0/q0) and (p1/q1) are the last two convergents
+		let (mut p0, mut q0, mut p1, mut q1) = (0u128, 1u128, 1u128, 0u128);
+		loop {
+			let a = rest.floor();
+			// an overflow here means the denominator is already far past the bound
+			let next = (a as u128).checked_mul(p1).and_then(|v| v.checked_add(p0))
+				.zip((a as u128).checked_mul(q1).and_then(|v| v.checked_add(q0)));
+			let (p2, q2) = match next {
+				Some((p2, q2)) if q2 <= max => (p2, q2),
+				_ => break,
+			};
+
+			(p0, q0, p1, q1) = (p1, q1, p2, q2);
+			if rest == a { break; }
+			rest = 1.0 / (rest - a);
+		}
+
+		// the largest semiconvergent that still fits under the bound
+		let k = (max - q0) / q1;
+		let (ps, qs) = (p0 + k * p1, q0 + k * q1);
+		let error = |p: u128, q: u128| (p as f64 / q as f64 - target).abs();
+		let (num, denom) = if error(ps, qs) < error(p1, q1) { (ps, qs) } else { (p1, q1) };
+
+		let num = BigInt::from(num);
+		let num = if x < 0.0 { -num } else { num };
+		Some(Self::from_products(&num, &BigInt::from(denom)))
+	}
+
+	/// Returns the terms of the finite continued fraction of the value.
+	///
+	/// The first term is the floor of the value, every later term is positive.
+	pub fn continued_fraction(&self) -> Vec<BigInt> {
+		ContinuedFraction::new(self).collect()
+	}
+
+	/// Returns an iterator over the convergents of the continued fraction,
+	/// the last one is equal to the value.
+	pub fn convergents(&self) -> Convergents {
+		Convergents {
+			terms: ContinuedFraction::new(self),
+			previous: (BigInt::zero(), BigInt::one()),
+			current: (BigInt::one(), BigInt::zero()),
+		}
+	}
+
+	// builds a reduced Rational from a numerator and denominator
+	fn from_products(num: &BigInt, denom: &BigInt) -> Self {
+		let mut ret_val = Self { nums: vec![num.clone()], denominators: vec![denom.clone()] };
+		ret_val.cancel_out();
+		ret_val
+	}
+}
+
+// MARK: Continued fractions
+
+// Euclid's algorithm on the numerator and denominator, yields each floor
+struct ContinuedFraction {
+	num: BigInt,
+	denom: BigInt,
+}
+
+impl ContinuedFraction {
+	fn new(value: &Rational) -> Self {
+		Self { num: value.numerator_big(), denom: value.denominator_big() }
+	}
+}
+
+impl Iterator for ContinuedFraction {
+	type Item = BigInt;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.denom.is_zero() { return None }
+
+		let (mut a, mut r) = self.num.div_rem(&self.denom);
+		if r.is_negative() {
+			a = &a - &BigInt::one();
+			r = &r + &self.denom;
+		}
+
+		self.num = std::mem::replace(&mut self.denom, r);
+		Some(a)
+	}
+}
+
+/// Iterator over the convergents of a Rational's continued fraction
+pub struct Convergents {
+	terms: ContinuedFraction,
+	previous: (BigInt, BigInt),
+	current: (BigInt, BigInt),
+}
+
+impl Iterator for Convergents {
+	type Item = Rational;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let a = self.terms.next()?;
+
+		let next = (
+			&(&a * &self.current.0) + &self.previous.0,
+			&(&a * &self.current.1) + &self.previous.1,
+		);
+		self.previous = std::mem::replace(&mut self.current, next);
+
+		Some(Rational::from_products(&self.current.0, &self.current.1))
+	}
+}
+
+// MARK: Parsing
+
+/// Error returned when a string can not be parsed as a Rational
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseRationalError {
+	/// the string is not an integer, fraction or decimal
+	Invalid,
+	/// the denominator of a fraction is zero
+	ZeroDenominator,
+}
+
+impl fmt::Display for ParseRationalError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Invalid => write!(f, "invalid rational literal"),
+			Self::ZeroDenominator => write!(f, "rational literal has a zero denominator"),
+		}
+	}
+}
+
+impl std::error::Error for ParseRationalError {}
+
+impl FromStr for Rational {
+	type Err = ParseRationalError;
+
+	/// Parses integers "5", fractions "-12/18" and decimals "0.375"
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let parse_int = |text: &str| text.parse::<BigInt>().map_err(|_| ParseRationalError::Invalid);
+
+		let (num, denom) = if let Some((num, denom)) = s.split_once('/') {
+			let denom = denom.trim();
+			if denom.starts_with(['-', '+']) { return Err(ParseRationalError::Invalid) }
+			(parse_int(num.trim())?, parse_int(denom)?)
+		}
+		else if let Some((whole, fraction)) = s.split_once('.') {
+			if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+				return Err(ParseRationalError::Invalid)
+			}
+			let (negative, whole) = match whole.strip_prefix('-') {
+				Some(rest) => (true, rest),
+				None => (false, whole.strip_prefix('+').unwrap_or(whole)),
+			};
+			let whole = match whole {
+				"" => BigInt::zero(),
+				digits if digits.bytes().all(|b| b.is_ascii_digit()) => parse_int(digits)?,
+				_ => return Err(ParseRationalError::Invalid),
+			};
+			let scale = BigInt::from(10).pow(fraction.len() as u32);
+			let num = &(&whole * &scale) + &parse_int(fraction)?;
+			(if negative { -num } else { num }, scale)
+		}
+		else {
+			(parse_int(s)?, BigInt::one())
+		};
+
+		if denom.is_zero() { return Err(ParseRationalError::ZeroDenominator) }
+
+		Ok(Self::from_products(&num, &denom))
+	}
+}
+
+impl fmt::Display for Rational {
+	/// Writes the value in lowest terms as "n/d", or just "n" for integers
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let num = self.numerator_big();
+		let denom = self.denominator_big();
+		let divisor = BigInt::gcd(&num, &denom);
+
+		let text = if denom == divisor { format!("{}", &num / &divisor) }
+			else { format!("{}/{}", &num / &divisor, &denom / &divisor) };
+
+		f.pad(&text)
+	}
+}
+
+// the primes of a factor, None if it has one too large for a PrimeFactorization
+fn factor_primes(factor: &BigInt) -> Option<PrimeFactorization> {
+	let (mut primes, rest) = PrimeFactorization::split_small_primes(factor);
+	// with no prime below 2^16 a rest that fits in a u32 is itself a prime
+	if rest != BigInt::one() {
+		let rest = u32::try_from(rest.to_u128()?).ok()?;
+		primes.multiply(&PrimeFactorization::of(rest));
+	}
+	Some(primes)
+}
+
+// divides each factor of nums and the factor of denominators it has a common
+// divisor with by that divisor, until no two factors across them share one
+fn cancel_common_divisors(nums: &mut [BigInt], denominators: &mut [BigInt]) {
+	for a in nums.iter_mut() {
+		for b in denominators.iter_mut() {
+			loop {
+				let divisor = BigInt::gcd(a, b);
+				if divisor == BigInt::one() { break }
+				*a = &*a / &divisor;
+				*b = &*b / &divisor;
+			}
+		}
+	}
+}
+
+impl From<&PrimeFactorization> for Rational {
+	fn from(value: &PrimeFactorization) -> Self {
+		let to_factors = |primes: Vec<u32>| primes.into_iter()
+			.map(BigInt::from)
+			.collect::<Vec<BigInt>>();
+
+		Self {
+			nums: to_factors(value.numerator_primes()),
+			denominators: to_factors(value.denominator_primes()),
+		}
+	}
 }
 
+impl From<i32> for Rational {
+	fn from(value: i32) -> Self {
+		Self::new_from_arrays(&[value], &[])
+	}
+}
+
+impl PartialEq for Rational {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Rational {}
+
+impl PartialOrd for Rational {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Rational {
+	fn cmp(&self, other: &Self) -> Ordering {
+		let left = &self.numerator_big() * &other.denominator_big();
+		let right = &other.numerator_big() * &self.denominator_big();
+
+		left.cmp(&right)
+	}
+}
+
+// MARK: Operators
+
+impl Neg for &Rational {
+	type Output = Rational;
+
+	fn neg(self) -> Self::Output {
+		let mut ret_val = self.clone();
+		ret_val.nums.push(BigInt::from(-1));
+		ret_val.cancel_out();
+		ret_val
+	}
+}
+
+impl Neg for Rational {
+	type Output = Rational;
+
+	fn neg(self) -> Self::Output {
+		-&self
+	}
+}
+
+impl Add for &Rational {
+	type Output = Rational;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		let (a, b) = (self.numerator_big(), self.denominator_big());
+		let (c, d) = (rhs.numerator_big(), rhs.denominator_big());
+		let divisor = BigInt::gcd(&b, &d);
+
+		let num = &(&a * &(&d / &divisor)) + &(&c * &(&b / &divisor));
+		let denom = &b * &(&d / &divisor);
+
+		Rational::from_products(&num, &denom)
+	}
+}
+
+impl Sub for &Rational {
+	type Output = Rational;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + &(-rhs)
+	}
+}
+
+impl Mul for &Rational {
+	type Output = Rational;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let mut ret_val = self.clone();
+		ret_val.multiply(rhs);
+		ret_val
+	}
+}
+
+impl Div for &Rational {
+	type Output = Rational;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		let mut ret_val = self.clone();
+		ret_val.divide(rhs);
+		ret_val
+	}
+}
+
+// forwards the owned versions of the operators to the borrowed ones
+macro_rules! forward_owned_op {
+	($op:ident, $method:ident) => {
+		impl $op for Rational {
+			type Output = Rational;
+
+			fn $method(self, rhs: Self) -> Self::Output {
+				(&self).$method(&rhs)
+			}
+		}
+	};
+}
+
+forward_owned_op!(Add, add);
+forward_owned_op!(Sub, sub);
+forward_owned_op!(Mul, mul);
+forward_owned_op!(Div, div);
+
 // MARK: Tests
 #[cfg(test)]
 mod tests {
 
 	use super::*;
 
+	fn big(factors: &[i32]) -> Vec<BigInt> {
+		factors.iter().map(|f| BigInt::from(*f)).collect()
+	}
 
 	fn test_new_from_arrays(
 		num: &[i32],
@@ -113,8 +602,8 @@ mod tests {
 	) {
 		let value = Rational::new_from_arrays(num, denom);
 
-		assert_eq!(value.nums, num);
-		assert_eq!(value.denominators, denom);
+		assert_eq!(value.nums, big(num));
+		assert_eq!(value.denominators, big(denom));
 	}
 
 	#[test] fn test_new_from_arrays_0() { test_new_from_arrays(
@@ -137,15 +626,45 @@ mod tests {
 	#[test]
 	fn test_initialize_rational_factorial() {
 		let value = Rational::factorial(5);
-		let expected = vec![1,2,3,4,5];
+		let expected = big(&[2,2,2,3,5]);
 
 		assert_eq!(value.nums.len(), expected.len());
 
-		for i in 0..5 {
-			assert_eq!(value.nums[i], expected[i]);
+		for (i, factor) in expected.iter().enumerate() {
+			assert_eq!(value.nums[i], *factor);
 		}
 	}
 
+	#[test] fn test_factorial_ratio_cancels_completely() {
+		let mut value = Rational::factorial(10);
+		value.divide(&Rational::factorial(8));
+		assert_eq!(value.nums, big(&[2,3,3,5]));
+		assert!(value.denominators.is_empty());
+
+		let mut value = Rational::factorial(10);
+		value.divide(&Rational::factorial(4));
+		value.divide(&Rational::factorial(6));
+		assert_eq!(value.nums, big(&[2,3,5,7]));
+		assert!(value.denominators.is_empty());
+	}
+
+	#[test] fn test_prime_factorization() {
+		let value = Rational::new_from_arrays(&[-12], &[45]);
+		let factors = value.prime_factorization().unwrap();
+		assert_eq!(factors.powers().collect::<Vec<_>>(), [(2,2), (3,-1), (5,-1)]);
+		assert_eq!(Rational::from(&factors), value.abs());
+		assert_eq!(Rational::from(0).prime_factorization(), None);
+	}
+
+	#[test] fn test_from_prime_above_i32() {
+		let mut factors = PrimeFactorization::of(4294967291);
+		factors.divide(&PrimeFactorization::of(4294967279));
+		let value = Rational::from(&factors);
+		assert_eq!(value.to_string(), "4294967291/4294967279");
+		assert_eq!(value.signum(), 1);
+		assert_eq!(value.prime_factorization(), Some(factors));
+	}
+
 	fn test_evaluate_factorial(value: u32, expected: i128) {
 		let value = Rational::factorial(value);
 
@@ -168,42 +687,42 @@ mod tests {
 	) {
 		let mut value = Rational::new();
 		for i in value_num {
-			value.nums.push(*i);
+			value.nums.push(BigInt::from(*i));
 		}
 		for i in value_denom {
-			value.denominators.push(*i);
+			value.denominators.push(BigInt::from(*i));
 		}
-		assert_eq!(value.nums, value_num);
-		assert_eq!(value.denominators, value_denom);
+		assert_eq!(value.nums, big(value_num));
+		assert_eq!(value.denominators, big(value_denom));
 
 		value.cancel_out();
 
-		assert_eq!(value.nums, result_num);
-		assert_eq!(value.denominators, result_denom);
+		assert_eq!(value.nums, big(result_num));
+		assert_eq!(value.denominators, big(result_denom));
 	}
 
 	#[test] fn test_cancel_out_0() {test_cancel_out(
 		&[1,1,2,2,3,3,5],
 		&[1,3,3],
-		&[1,2,2,5],
+		&[2,2,5],
 		&[0; 0]
 	)}
 	#[test] fn test_cancel_out_1() {test_cancel_out(
 		&[1,1,2,2,3,3,5],
 		&[1,3,3,7],
-		&[1,2,2,5],
+		&[2,2,5],
 		&[7]
 	)}
 	#[test] fn test_cancel_out_2() {test_cancel_out(
 		&[0; 0],
 		&[1,3,3],
 		&[0; 0],
-		&[1,3,3]
+		&[3,3]
 	)}
 	#[test] fn test_cancel_out_3() {test_cancel_out(
 		&[1,1,2,2,3,3,5],
 		&[0; 0],
-		&[1,1,2,2,3,3,5],
+		&[2,2,3,3,5],
 		&[0; 0]
 	)}
 	#[test] fn test_cancel_out_4() {test_cancel_out(
@@ -212,6 +731,30 @@ mod tests {
 		&[0; 0],
 		&[3]
 	)}
+	#[test] fn test_cancel_out_5() {test_cancel_out(
+		&[4],
+		&[6],
+		&[2],
+		&[3]
+	)}
+	#[test] fn test_cancel_out_6() {test_cancel_out(
+		&[-12],
+		&[18],
+		&[-1,2],
+		&[3]
+	)}
+	#[test] fn test_cancel_out_7() {test_cancel_out(
+		&[0,7],
+		&[3],
+		&[0],
+		&[0; 0]
+	)}
+	#[test] fn test_cancel_out_8() {test_cancel_out(
+		&[10,9],
+		&[6,15],
+		&[0; 0],
+		&[0; 0]
+	)}
 	
 	fn test_multiply(
 		value_1_num: &[i32],
@@ -226,8 +769,8 @@ mod tests {
 
 		value_1.multiply(&value_2);
 
-		assert_eq!(value_1.nums, result_num);
-		assert_eq!(value_1.denominators, result_denom);
+		assert_eq!(value_1.nums, big(result_num));
+		assert_eq!(value_1.denominators, big(result_denom));
 	}
 
 	#[test] fn test_multiply_0() { test_multiply(
@@ -235,7 +778,7 @@ mod tests {
 		&[0;0],
 		&[2],
 		&[0;0],
-		&[1,2,2,3],
+		&[2,2,3],
 		&[0;0]
 	);}
 	#[test] fn test_multiply_1() { test_multiply(
@@ -243,7 +786,7 @@ mod tests {
 		&[0;0],
 		&[0; 0],
 		&[0;0],
-		&[1,2,3],
+		&[2,3],
 		&[0;0]
 	);}
 	#[test] fn test_multiply_2() { test_multiply(
@@ -251,8 +794,16 @@ mod tests {
 		&[0;0],
 		&[0;0],
 		&[5,6],
-		&[1,2,3],
-		&[5,6]
+		&[0; 0],
+		&[5]
+	);}
+	#[test] fn test_multiply_3() { test_multiply(
+		&[-2,3],
+		&[0;0],
+		&[1],
+		&[-4],
+		&[3],
+		&[2]
 	);}
 
 	fn test_divide(
@@ -268,8 +819,8 @@ mod tests {
 
 		value_1.divide(&value_2);
 
-		assert_eq!(value_1.nums, result_num);
-		assert_eq!(value_1.denominators, result_denom);
+		assert_eq!(value_1.nums, big(result_num));
+		assert_eq!(value_1.denominators, big(result_denom));
 	}
 
 	#[test] fn test_divide_0() { test_divide(
@@ -277,7 +828,7 @@ mod tests {
 		&[0;0],
 		&[2],
 		&[0;0],
-		&[1,3],
+		&[3],
 		&[0;0]
 	);}
 	#[test] fn test_divide_1() { test_divide(
@@ -285,7 +836,7 @@ mod tests {
 		&[0;0],
 		&[0;0],
 		&[1],
-		&[1,1,2,3],
+		&[2,3],
 		&[0;0]
 	);}
 	#[test] fn test_divide_2() { test_divide(
@@ -301,8 +852,267 @@ mod tests {
 		&[4,5],
 		&[2],
 		&[4,8],
-		&[1,3,8],
+		&[2,2,2,3],
 		&[5]
 	);}
 
-}
\ No newline at end of file
+// region: test_arithmetic
+	fn test_add(a: (i32, i32), b: (i32, i32), expected: (i32, i32)) {
+		let sum = Rational::new_from_fraction(a.0, a.1) + Rational::new_from_fraction(b.0, b.1);
+		assert_eq!(sum, Rational::new_from_fraction(expected.0, expected.1));
+		assert_eq!((sum.numerator(), sum.denominator()), (expected.0 as i128, expected.1 as i128));
+	}
+
+	#[test] fn test_add_0() { test_add((1,2), (1,3), (5,6)); }
+	#[test] fn test_add_1() { test_add((1,2), (-1,2), (0,1)); }
+	#[test] fn test_add_2() { test_add((3,4), (-5,6), (-1,12)); }
+	#[test] fn test_add_3() { test_add((7,1), (2,1), (9,1)); }
+
+	fn test_sub(a: (i32, i32), b: (i32, i32), expected: (i32, i32)) {
+		let difference = Rational::new_from_fraction(a.0, a.1) - Rational::new_from_fraction(b.0, b.1);
+		assert_eq!(difference, Rational::new_from_fraction(expected.0, expected.1));
+	}
+
+	#[test] fn test_sub_0() { test_sub((1,2), (1,3), (1,6)); }
+	#[test] fn test_sub_1() { test_sub((1,3), (1,2), (-1,6)); }
+
+	fn test_mul_div(a: (i32, i32), b: (i32, i32), product: (i32, i32), quotient: (i32, i32)) {
+		let a = Rational::new_from_fraction(a.0, a.1);
+		let b = Rational::new_from_fraction(b.0, b.1);
+		assert_eq!(&a * &b, Rational::new_from_fraction(product.0, product.1));
+		assert_eq!(a / b, Rational::new_from_fraction(quotient.0, quotient.1));
+	}
+
+	#[test] fn test_mul_div_0() { test_mul_div((2,3), (3,4), (1,2), (8,9)); }
+	#[test] fn test_mul_div_1() { test_mul_div((-2,3), (3,-4), (1,2), (8,9)); }
+	#[test] fn test_mul_div_2() { test_mul_div((0,5), (3,4), (0,1), (0,1)); }
+
+	#[test] #[should_panic] fn test_divide_by_zero() {
+		let _ = Rational::from(1) / Rational::from(0);
+	}
+
+	#[test] fn test_large_sum() {
+		let big = Rational::new_from_fraction(i32::MAX, 1);
+		let sum = &big + &big;
+		assert_eq!(sum.numerator(), 2 * i32::MAX as i128);
+	}
+
+	#[test] fn test_sum_of_large_prime() {
+		// 2147483659 is a prime above i32::MAX
+		let sum = Rational::from(i32::MAX) + Rational::from(12);
+		assert_eq!(sum.numerator(), 2147483659);
+		assert_eq!(&sum - &Rational::from(12), Rational::from(i32::MAX));
+		assert_eq!(Rational::from(-12) - Rational::from(i32::MAX), -sum.clone());
+		assert_eq!(sum.prime_factorization().unwrap().powers().collect::<Vec<_>>(), [(2147483659, 1)]);
+
+		let halves = Rational::new_from_fraction(i32::MAX, 2) + Rational::new_from_fraction(13, 2);
+		assert_eq!(halves, Rational::from(1073741830));
+	}
+
+	#[test] fn test_sum_beyond_u64() {
+		// (2^31 - 1)^3 + 46 is a prime above u64::MAX
+		let big = Rational::from(i32::MAX);
+		let cube = &(&big * &big) * &big;
+		let sum = &cube + &Rational::from(46);
+		assert_eq!(sum.to_string(), "9903520300447984150353281069");
+
+		// the large prime cancels against itself and the cube against its own factors
+		assert_eq!(&sum / &sum, Rational::from(1));
+		let ratio = &(&sum * &big) / &(&sum * &cube);
+		assert_eq!(ratio, (&big * &big).recip());
+		assert_eq!((&sum - &cube).evaluate(), 46);
+
+		let mut product = Rational::new_from_arrays(&[3], &[7]);
+		product.multiply(&sum);
+		product.divide(&sum);
+		assert_eq!(product, Rational::new_from_fraction(3, 7));
+	}
+
+	#[test] fn test_convergents_of_large_prime() {
+		let value = &(Rational::from(i32::MAX) + Rational::from(12)) / &Rational::from(7);
+		assert_eq!(value.convergents().last(), Some(value));
+	}
+
+	#[test] fn test_large_product_sum() {
+		let big = Rational::factorial(30);
+		let sum = &big + &big;
+		assert_eq!(sum.evaluate_big(), &BigInt::from(2) * &Rational::factorial(30).evaluate_big());
+		assert!(sum > big);
+	}
+// endregion:
+
+// region: test_evaluate_checked
+	fn test_evaluate_checked(value: Rational, expected: Option<i128>) {
+		assert_eq!(value.evaluate_checked(), expected)
+	}
+
+	#[test] fn test_evaluate_checked_0() { test_evaluate_checked(Rational::factorial(33), Some(8683317618811886495518194401280000000)); }
+	#[test] fn test_evaluate_checked_1() { test_evaluate_checked(Rational::factorial(34), None); }
+	#[test] fn test_evaluate_checked_2() { test_evaluate_checked(Rational::new_from_arrays(&[7], &[2]), Some(3)); }
+	#[test] fn test_evaluate_checked_3() {
+		let mut value = Rational::factorial(40);
+		value.divide(&Rational::factorial(38));
+		test_evaluate_checked(value, Some(1560));
+	}
+	#[test] fn test_evaluate_checked_4() { test_evaluate_checked(Rational::new(), Some(1)); }
+	#[test] fn test_evaluate_checked_5() { test_evaluate_checked(Rational::new_from_arrays(&[0; 0], &[3]), Some(0)); }
+
+	fn test_evaluate_big(value: Rational, expected: &str) {
+		assert_eq!(value.evaluate_big().to_string(), expected)
+	}
+
+	#[test] fn test_evaluate_big_0() { test_evaluate_big(Rational::factorial(0), "1"); }
+	#[test] fn test_evaluate_big_1() { test_evaluate_big(
+		Rational::factorial(40),
+		"815915283247897734345611269596115894272000000000"
+	);}
+	#[test] fn test_evaluate_big_2() { test_evaluate_big(Rational::new_from_fraction(-9, 2), "-4"); }
+
+	#[test] #[should_panic] fn test_evaluate_overflow() {
+		Rational::factorial(40).evaluate();
+	}
+// endregion:
+
+// region: test_ordering
+	fn test_cmp(a: (i32, i32), b: (i32, i32), expected: Ordering) {
+		let a = Rational::new_from_fraction(a.0, a.1);
+		let b = Rational::new_from_fraction(b.0, b.1);
+		assert_eq!(a.cmp(&b), expected);
+	}
+
+	#[test] fn test_cmp_0() { test_cmp((1,2), (2,4), Ordering::Equal); }
+	#[test] fn test_cmp_1() { test_cmp((1,3), (1,2), Ordering::Less); }
+	#[test] fn test_cmp_2() { test_cmp((-1,2), (-1,3), Ordering::Less); }
+	#[test] fn test_cmp_3() { test_cmp((3,-4), (-3,4), Ordering::Equal); }
+
+	#[test] fn test_factor_lists_compare_by_value() {
+		assert_eq!(Rational::new_from_arrays(&[2,3], &[4]), Rational::new_from_arrays(&[6], &[4]));
+		assert_eq!(Rational::new(), Rational::from(1));
+	}
+// endregion:
+
+// region: test_sign
+	fn test_sign(num: i32, denom: i32, expected_sign: i32, expected_abs: (i32, i32)) {
+		let value = Rational::new_from_fraction(num, denom);
+		assert_eq!(value.signum(), expected_sign);
+		assert_eq!(value.abs(), Rational::new_from_fraction(expected_abs.0, expected_abs.1));
+		assert_eq!(-(-value.clone()), value);
+	}
+
+	#[test] fn test_sign_0() { test_sign(-12, 18, -1, (2,3)); }
+	#[test] fn test_sign_1() { test_sign(12, -18, -1, (2,3)); }
+	#[test] fn test_sign_2() { test_sign(-12, -18, 1, (2,3)); }
+	#[test] fn test_sign_3() { test_sign(0, -18, 0, (0,1)); }
+
+	#[test] fn test_recip() {
+		assert_eq!(Rational::new_from_fraction(-3, 4).recip(), Rational::new_from_fraction(-4, 3));
+	}
+// endregion:
+
+// region: test_to_f64
+	fn test_to_f64(value: Rational, expected: f64) {
+		assert_eq!(value.to_f64(), expected)
+	}
+
+	#[test] fn test_to_f64_0() { test_to_f64(Rational::new_from_fraction(3, 2), 1.5); }
+	#[test] fn test_to_f64_1() { test_to_f64(Rational::new_from_fraction(-3, 8), -0.375); }
+	#[test] fn test_to_f64_2() { test_to_f64(Rational::factorial(5), 120.0); }
+	#[test] fn test_to_f64_3() { test_to_f64(Rational::new_from_arrays(&[-1], &[2; 1074]), -f64::from_bits(1)); }
+	#[test] fn test_to_f64_4() { test_to_f64(Rational::new_from_arrays(&[2; 1024], &[3]), 2f64.powi(1023) / 1.5); }
+
+	#[test] fn test_to_f64_large_factors() {
+		// both products overflow an f64 but their ratio is close to one
+		let value = Rational::new_from_arrays(&[3; 1000], &[2; 1585]);
+		let expected = (1000.0 * 3f64.ln() - 1585.0 * 2f64.ln()).exp();
+		assert!((value.to_f64() - expected).abs() < 1e-12 * expected);
+	}
+// endregion:
+
+// region: test_display
+	fn test_display(value: Rational, expected: &str) {
+		assert_eq!(value.to_string(), expected)
+	}
+
+	#[test] fn test_display_0() { test_display(Rational::new_from_fraction(3, 4), "3/4"); }
+	#[test] fn test_display_1() { test_display(Rational::new_from_arrays(&[-12], &[18]), "-2/3"); }
+	#[test] fn test_display_2() { test_display(Rational::new_from_arrays(&[12], &[-4]), "-3"); }
+	#[test] fn test_display_3() { test_display(Rational::from(0), "0"); }
+	#[test] fn test_display_4() { test_display(Rational::factorial(25), "15511210043330985984000000"); }
+
+	#[test] fn test_display_padding() {
+		assert_eq!(format!("[{:>6}]", Rational::new_from_fraction(1, 2)), "[   1/2]");
+	}
+// endregion:
+
+// region: test_from_str
+	fn test_from_str(text: &str, expected: Result<(i32, i32), ParseRationalError>) {
+		let expected = expected.map(|(n, d)| Rational::new_from_fraction(n, d));
+		assert_eq!(text.parse::<Rational>(), expected)
+	}
+
+	#[test] fn test_from_str_0() { test_from_str("3/4", Ok((3, 4))); }
+	#[test] fn test_from_str_1() { test_from_str("-12/18", Ok((-2, 3))); }
+	#[test] fn test_from_str_2() { test_from_str("0.375", Ok((3, 8))); }
+	#[test] fn test_from_str_3() { test_from_str("-1.25", Ok((-5, 4))); }
+	#[test] fn test_from_str_4() { test_from_str(" 42 ", Ok((42, 1))); }
+	#[test] fn test_from_str_5() { test_from_str("-.5", Ok((-1, 2))); }
+	#[test] fn test_from_str_6() { test_from_str("1/0", Err(ParseRationalError::ZeroDenominator)); }
+	#[test] fn test_from_str_7() { test_from_str("1/-2", Err(ParseRationalError::Invalid)); }
+	#[test] fn test_from_str_8() { test_from_str("--3.5", Err(ParseRationalError::Invalid)); }
+	#[test] fn test_from_str_9() { test_from_str("1.", Err(ParseRationalError::Invalid)); }
+	#[test] fn test_from_str_10() { test_from_str("abc", Err(ParseRationalError::Invalid)); }
+	#[test] fn test_from_str_11() { test_from_str("", Err(ParseRationalError::Invalid)); }
+
+	#[test] fn test_from_str_large_prime() {
+		// 4294967311 is a prime above u32::MAX
+		let value: Rational = "4294967311/3".parse().unwrap();
+		assert_eq!(value.to_string(), "4294967311/3");
+		assert_eq!(value.prime_factorization(), None);
+	}
+
+	#[test] fn test_from_str_round_trip() {
+		let value = Rational::factorial(20) / Rational::factorial(7).recip();
+		assert_eq!(value.to_string().parse::<Rational>(), Ok(value));
+	}
+// endregion:
+
+// region: test_from_f64_approx
+	fn test_from_f64_approx(x: f64, max_denominator: u32, expected: Option<(i32, i32)>) {
+		let expected = expected.map(|(n, d)| Rational::new_from_fraction(n, d));
+		assert_eq!(Rational::from_f64_approx(x, max_denominator), expected)
+	}
+
+	#[test] fn test_from_f64_approx_0() { test_from_f64_approx(0.375, 100, Some((3, 8))); }
+	#[test] fn test_from_f64_approx_1() { test_from_f64_approx(std::f64::consts::PI, 10, Some((22, 7))); }
+	#[test] fn test_from_f64_approx_2() { test_from_f64_approx(std::f64::consts::PI, 1000, Some((355, 113))); }
+	#[test] fn test_from_f64_approx_3() { test_from_f64_approx(-1.0 / 3.0, 10, Some((-1, 3))); }
+	#[test] fn test_from_f64_approx_4() { test_from_f64_approx(2.5, 1, Some((2, 1))); }
+	#[test] fn test_from_f64_approx_5() { test_from_f64_approx(0.0, 5, Some((0, 1))); }
+	#[test] fn test_from_f64_approx_6() { test_from_f64_approx(f64::NAN, 5, None); }
+	#[test] fn test_from_f64_approx_7() { test_from_f64_approx(1.0, 0, None); }
+	// 0.7 lies between the convergents 2/3 and 1/1, the semiconvergent 3/4 is not closer than 2/3
+	#[test] fn test_from_f64_approx_8() { test_from_f64_approx(0.7, 4, Some((2, 3))); }
+	// 0.26 has convergents 0, 1/3, 1/4 and with a bound of 3 only 1/3 qualifies as best
+	#[test] fn test_from_f64_approx_9() { test_from_f64_approx(0.26, 3, Some((1, 3))); }
+// endregion:
+
+// region: test_continued_fraction
+	fn test_continued_fraction(value: Rational, expected: &[i32]) {
+		let expected: Vec<BigInt> = expected.iter().map(|a| BigInt::from(*a)).collect();
+		assert_eq!(value.continued_fraction(), expected)
+	}
+
+	#[test] fn test_continued_fraction_0() { test_continued_fraction(Rational::new_from_fraction(415, 93), &[4,2,6,7]); }
+	#[test] fn test_continued_fraction_1() { test_continued_fraction(Rational::new_from_fraction(-7, 3), &[-3,1,2]); }
+	#[test] fn test_continued_fraction_2() { test_continued_fraction(Rational::from(5), &[5]); }
+
+	#[test] fn test_convergents() {
+		let convergents: Vec<String> = Rational::new_from_fraction(415, 93)
+			.convergents()
+			.map(|c| c.to_string())
+			.collect();
+		assert_eq!(convergents, ["4", "9/2", "58/13", "415/93"]);
+	}
+// endregion:
+
+}
@@ -55,13 +55,17 @@ impl<T: Clone + Copy + Display> RkgTabN<T> {
 	}
 
 	/// set labels from flat array and axis_shapes
-	/// panics if labels not correct size for axis, or is axis is out of bounds
+	/// the labels of each axis follow each other, so there are as many labels
+	/// as the lengths of the axes added together
+	/// panics if labels not correct size for axis, if an axis is out of bounds,
+	/// or if the axes are not in increasing order without repeats
 	pub fn set_labels_from_flat(&mut self, labels: &[String], axis: &[usize]) {
 		if axis.len() > self.dims { panic!("Number of axis is greater than number of dimensions"); }
-		let mut expected_count = 1;
+		if axis.windows(2).any(|pair| pair[0] >= pair[1]) { panic!("axis must be in increasing order without repeats"); }
+		let mut expected_count = 0;
 		for a in axis.iter() {
-			if *a > self.shape.len() { panic!("axis is out of bounds"); }
-			expected_count *= self.shape[*a];
+			if *a >= self.shape.len() { panic!("axis is out of bounds"); }
+			expected_count += self.shape[*a];
 		}
 
 		if expected_count != labels.len() { panic!("Count of labels does not match shape of selected axis"); }
@@ -70,6 +74,12 @@ impl<T: Clone + Copy + Display> RkgTabN<T> {
 		self.label_axis = Vec::from(axis);
 	}
 
+	/// returns all the labels of an axis, None if the axis has no labels
+	pub fn labels_by_axis(&self, axis: usize) -> Option<Vec<String>> {
+		if !self.label_axis.contains(&axis) { return None }
+		Some((0..self.shape[axis]).map(|i| self.get_label_by_axis(axis, i)).collect())
+	}
+
 	/// Get label using index and axis
 	pub fn get_label_by_axis(&self, axis: usize, i:usize) -> String {
 		if axis >= self.shape.len() { panic!("axis is out of bounds"); }
@@ -111,6 +121,85 @@ impl<T: Clone + Copy + Display> RkgTabN<T> {
 		return self.data[flat_i]
 	}
 
+	/// returns a table with axis removed, each value is f applied to the values
+	/// along that axis, labels of the other axes are kept
+	/// reducing a 1d table gives a table with a single value
+	/// panics if axis is out of bounds
+	pub fn reduce_axis<U: Clone + Copy + Display>(&self, axis: usize, f: impl Fn(&[T]) -> U) -> RkgTabN<U> {
+		if axis >= self.shape.len() { panic!("axis is out of bounds"); }
+
+		let stride: usize = self.shape[axis+1..].iter().product();
+		let outer: usize = self.shape[..axis].iter().product();
+		let length = self.shape[axis];
+
+		let mut data = Vec::with_capacity(outer * stride);
+		let mut lane = Vec::with_capacity(length);
+		for o in 0..outer {
+			for s in 0..stride {
+				lane.clear();
+				lane.extend((0..length).map(|i| self.data[(o * length + i) * stride + s]));
+				data.push(f(&lane));
+			}
+		}
+
+		let mut shape = self.shape.clone();
+		shape.remove(axis);
+
+		// labels are stored one axis after another, so keep the runs of the other axes
+		let mut labels = Vec::new();
+		let mut label_axis = Vec::new();
+		let mut offset = 0;
+		for a in 0..self.shape.len() {
+			if !self.label_axis.contains(&a) { continue }
+			if a != axis {
+				labels.extend_from_slice(&self.labels[offset..offset+self.shape[a]]);
+				label_axis.push(if a > axis { a - 1 } else { a });
+			}
+			offset += self.shape[a];
+		}
+
+		if shape.is_empty() { shape.push(1); }
+
+		RkgTabN {
+			data,
+			dims: shape.len(),
+			shape,
+			labels,
+			label_axis
+		}
+	}
+
+	/// returns a table of the given rows in the given order, rows can repeat
+	/// panics if a row is out of bounds
+	pub fn select_rows(&self, rows: &[usize]) -> Self {
+		let width: usize = self.shape[1..].iter().product();
+
+		let mut data = Vec::with_capacity(rows.len() * width);
+		for r in rows {
+			if *r >= self.shape[0] { panic!("row is out of bounds"); }
+			data.extend_from_slice(&self.data[r*width..(r+1)*width]);
+		}
+
+		// row labels come first so they are replaced, the rest are kept
+		let mut labels = self.labels.clone();
+		if self.label_axis.contains(&0) {
+			let rest = labels.split_off(self.shape[0]);
+			labels = rows.iter().map(|r| self.labels[*r].clone()).collect();
+			labels.extend(rest);
+		}
+
+		let mut shape = self.shape.clone();
+		shape[0] = rows.len();
+
+		Self {
+			data,
+			dims: self.dims,
+			shape,
+			labels,
+			label_axis: self.label_axis.clone()
+		}
+	}
+
 	/// drops extra dimensions, drops values that dont fit in new size
 	pub fn resize(_s: Vec<usize>) {
 
@@ -128,6 +217,10 @@ impl<T: Clone + Copy + Display> RkgTabN<T> {
 
 		let mut col_width = 1;
 
+		// row labels go in a first collumn as wide as the longest one
+		let row_labels = self.labels_by_axis(row);
+		let row_width = row_labels.as_ref().map_or(0, |l| l.iter().map(|l| l.len()).max().unwrap_or(0) + 2);
+
 		if self.label_axis.contains(&col) {
 			let mut labels: Vec<String> = Vec::new();
 			for l in 0..self.shape[col] {
@@ -140,6 +233,10 @@ impl<T: Clone + Copy + Display> RkgTabN<T> {
 
 			ret_string.push('|');
 
+			if row_labels.is_some() {
+				ret_string.push_str(&format!("{0:1$}|", "", row_width));
+			}
+
 			for l in labels {
 				ret_string.push_str(&format!("{0:^1$}|", l, col_width));
 			}
@@ -158,6 +255,9 @@ impl<T: Clone + Copy + Display> RkgTabN<T> {
 
 		for i in 0..self.shape[row] {
 			ret_string.push('|');
+			if let Some(labels) = &row_labels {
+				ret_string.push_str(&format!("{0:^1$}|", labels[i], row_width));
+			}
 			for j in 0..self.shape[col] {
 				cords[row] = i;
 				cords[col] = j;
@@ -231,4 +331,62 @@ mod tests {
 		if DISPLAY { println!("{tab_string}") }
 	}
 
-}
\ No newline at end of file
+	#[test] fn test_reduce_axis() {
+		let mut table = RkgTabN::from_flat(&[1,2,3,4,5,6], &[3,2]);
+		table.set_labels_from_flat(&["X axis".to_string(), "Y axis".to_string()], &[1]);
+
+		let sums = table.reduce_axis(0, |lane| lane.iter().sum::<i32>());
+		assert_eq!(sums.shape(), vec![2]);
+		assert_eq!(sums.get(&[0]), 9);
+		assert_eq!(sums.get(&[1]), 12);
+		assert_eq!(sums.get_label_by_axis(0, 1), "Y axis");
+
+		let rows = table.reduce_axis(1, |lane| lane[1] - lane[0]);
+		assert_eq!(rows.shape(), vec![3]);
+		assert_eq!(rows.get(&[2]), 1);
+
+		let total = sums.reduce_axis(0, |lane| lane.iter().sum::<i32>());
+		assert_eq!(total.shape(), vec![1]);
+		assert_eq!(total.get(&[0]), 21);
+	}
+
+	#[test] fn test_row_and_collumn_labels() {
+		let mut table = RkgTabN::from_flat(&[1,2,3,4], &[2,2]);
+		let labels: Vec<String> = ["a", "b", "x", "y"].iter().map(|l| l.to_string()).collect();
+		table.set_labels_from_flat(&labels, &[0,1]);
+		assert_eq!(table.labels_by_axis(0), Some(vec!["a".to_string(), "b".to_string()]));
+		assert_eq!(table.get_label_by_axis(1, 1), "y");
+		assert_eq!(RkgTabN::from_flat(&[1,2], &[2]).labels_by_axis(0), None);
+
+		let tab_string = table.table2d_as_string(0,1);
+		assert_eq!(tab_string.lines().nth(2), Some("| a | 1 | 2 |"));
+		if DISPLAY { println!("{tab_string}") }
+	}
+
+	#[test] fn test_select_rows() {
+		let mut table = RkgTabN::from_flat(&[1,2,3,4,5,6], &[3,2]);
+		let labels: Vec<String> = ["r0", "r1", "r2", "x", "y"].iter().map(|l| l.to_string()).collect();
+		table.set_labels_from_flat(&labels, &[0,1]);
+
+		let selected = table.select_rows(&[2,0,2]);
+		assert_eq!(selected.shape(), vec![3,2]);
+		assert_eq!(selected.get(&[0,1]), 6);
+		assert_eq!(selected.get(&[1,0]), 1);
+		assert_eq!(selected.get_label_by_axis(0, 2), "r2");
+		assert_eq!(selected.get_label_by_axis(1, 0), "x");
+	}
+
+	fn test_set_labels_from_flat(shape: &[usize], labels: &[&str], axis: &[usize]) {
+		let mut table = RkgTabN::from_flat(&vec![0; shape.iter().product()], shape);
+		let labels: Vec<String> = labels.iter().map(|l| l.to_string()).collect();
+		table.set_labels_from_flat(&labels, axis);
+	}
+
+	// one label for each row and each collumn, not for each cell
+	#[test] fn test_set_labels_from_flat_0() { test_set_labels_from_flat(&[2,3], &["a", "b", "x", "y", "z"], &[0,1]); }
+	#[test] #[should_panic] fn test_set_labels_from_flat_1() { test_set_labels_from_flat(&[2,2], &["a", "b", "x", "y"], &[1,0]); }
+	#[test] #[should_panic] fn test_set_labels_from_flat_2() { test_set_labels_from_flat(&[2,2], &["a", "b", "x", "y"], &[1,1]); }
+	#[test] #[should_panic] fn test_set_labels_from_flat_3() { test_set_labels_from_flat(&[2,2], &["a", "b"], &[2]); }
+	#[test] #[should_panic] fn test_set_labels_from_flat_4() { test_set_labels_from_flat(&[2,3], &["a", "b", "c", "d", "e", "f"], &[0,1]); }
+
+}
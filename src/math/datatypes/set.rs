@@ -1,3 +1,11 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::math::datatypes::rational::Rational;
+
 // MARK: TSetElement trait
 /// This is a trait that wraps together orther traits that the generic T
 /// is required to have to be used in a TSet set
@@ -19,6 +27,10 @@ pub trait TSet<'a, T> {
 	/// returns a new Set intilized as a subset of anouther set
 	fn subset_from_range(&'a self, start: usize, end: usize) -> Self;
 
+	/// returns a new Set intilized as a subset of anouther set using the
+	/// elements at the given indices, in the order the indices are given
+	fn subset_from_indices(&'a self, indices: &[usize]) -> Self;
+
 	/// returns the usize of the set
 	fn size(&self) -> usize;
 
@@ -28,25 +40,154 @@ pub trait TSet<'a, T> {
 	/// returns the usize of the parent space if is exisists
 	fn parent_size(&self) -> Option<usize>;
 
+	/// returns true if both sets are subsets of the same parent space,
+	/// a set that is not a subset is its own space
+	fn shares_parent(&self, other: &Self) -> bool;
+
 	/// returns true if set is contained in other
 	fn is_subset_of(&self, other: &Self) -> bool;
 	
 	/// returns a set that is the union of a and b
-	/// will also modifiy the parent set if it exists
+	/// the result shares the parent space of a, or of b if a has none
 	fn union(a: &Self, b: &Self) -> Self;
+
+	/// returns a set of the elements that are in both a and b
+	/// the result shares the parent space of a, or of b if a has none
+	fn intersection(a: &Self, b: &Self) -> Self;
+
+	/// returns a set of the elements of a that are not in b
+	/// the result shares the parent space of a, or of b if a has none
+	fn difference(a: &Self, b: &Self) -> Self;
+
+	/// returns a set of the elements that are in a or b but not both
+	/// the result shares the parent space of a, or of b if a has none
+	fn symmetric_difference(a: &Self, b: &Self) -> Self;
+
+	/// returns the elements of the parent space that are not in this set
+	/// returns None if the set has no parent space
+	fn complement(&self) -> Option<Self> where Self: Sized;
+
+	/// returns true if element is in the set
+	fn contains(&self, element: &T) -> bool;
+
+	/// returns true if the set has no elements in common with other
+	fn is_disjoint(&self, other: &Self) -> bool;
+
+	/// returns true if other is contained in this set
+	fn is_superset_of(&self, other: &Self) -> bool;
+
+	/// returns the subset of elements that satisfy the predicate, with this
+	/// set as its parent so it can be used as an event of this space
+	fn filter<F: Fn(&T) -> bool>(&'a self, predicate: F) -> Self;
+
+	/// Lazy iterator over every subset of the set, each with this set as its
+	/// parent.  There are 2^n of them so only the current one is held in memory
+	fn power_set(&'a self) -> PowerSet<'a, T, Self>
+	where Self: Sized {
+		PowerSet { set: self, included: vec![false; self.size()], done: false, element: PhantomData }
+	}
 }
 
+// MARK: Generators
+// region: Generators
+/// Lazy iterator over the power set of a set.
+///
+/// Subsets are yielded in binary counting order of which positions they
+/// include, starting with the empty set and ending with the whole set.
+pub struct PowerSet<'a, T, S> {
+	set: &'a S,
+	included: Vec<bool>,
+	done: bool,
+	element: PhantomData<T>,
+}
+
+impl<'a, T, S: TSet<'a, T>> Iterator for PowerSet<'a, T, S> {
+	type Item = S;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None }
+
+		let indices: Vec<usize> = (0..self.included.len()).filter(|i| self.included[*i]).collect();
+
+		// add one to the binary counter, it is done once every digit carries
+		match self.included.iter().position(|x| !x) {
+			None => self.done = true,
+			Some(i) => {
+				self.included[..i].fill(false);
+				self.included[i] = true;
+			}
+		}
+
+		Some(self.set.subset_from_indices(&indices))
+	}
+}
+
+/// Lazy iterator over the Cartesian product of any number of sets.
+///
+/// Each item holds one element from every set, in the order the sets were
+/// given.  The last set changes fastest, like the digits of a counter.
+pub struct CartesianProduct<'a, T> {
+	sets: Vec<&'a VecSet<T>>,
+	indices: Vec<usize>,
+	done: bool,
+}
+
+/// returns a lazy iterator over the Cartesian product of the sets
+/// the product of no sets has a single empty item
+pub fn cartesian_product<'a, T: TSetElement>(sets: &[&'a VecSet<T>]) -> CartesianProduct<'a, T> {
+	CartesianProduct {
+		sets: sets.to_vec(),
+		indices: vec![0; sets.len()],
+		done: sets.iter().any(|s| s.size() == 0),
+	}
+}
+
+/// returns a lazy iterator over the ordered pairs of a and b, for sets
+/// whose elements have different types
+pub fn cartesian_pairs<'a, A: TSetElement, B: TSetElement>(
+	a: &'a VecSet<A>,
+	b: &'a VecSet<B>
+) -> impl Iterator<Item = (A, B)> + 'a {
+	a.set.iter().flat_map(move |x| b.set.iter().map(move |y| (*x, *y)))
+}
+
+impl<'a, T: TSetElement> Iterator for CartesianProduct<'a, T> {
+	type Item = Vec<T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None }
+
+		let ret_val = self.sets.iter().zip(&self.indices).map(|(s, i)| s[*i]).collect();
+
+		// move the rightmost index that is not at the end of its set
+		match (0..self.sets.len()).rev().find(|i| self.indices[*i] + 1 < self.sets[*i].size()) {
+			None => self.done = true,
+			Some(i) => {
+				self.indices[i] += 1;
+				self.indices[i+1..].fill(0);
+			}
+		}
+
+		Some(ret_val)
+	}
+}
+// endregion:
+
 // MARK: VecSet
 // region: VecSet
 /// Implementation of a set using vec internally
-pub struct VecSet<'a, T> {
-	set: Vec<T>,
-	// TODO make parent an enum so it can be pointer to other VecSet, a Vec or None
-	parent: Option<&'a VecSet<'a, T>>
+///
+/// Every element is unique, repeated elements are dropped when the set is
+/// built and the first occurrence keeps its place.  Use `MultiSet` when
+/// elements can repeat.
+pub struct VecSet<T> {
+	set: Rc<Vec<T>>,
+	// shared handle to the elements of the space this is a subset of
+	parent: Option<Rc<Vec<T>>>
 }
 
 // Gives Set the ability to use slices
-impl<'a, T, Idx> std::ops::Index<Idx> for VecSet<'a, T>
+impl<T, Idx> std::ops::Index<Idx> for VecSet<T>
 where Idx: std::slice::SliceIndex<[T]>, {
 	type Output = Idx::Output;
 
@@ -55,23 +196,74 @@ where Idx: std::slice::SliceIndex<[T]>, {
 	}
 }
 
-impl<'a, T: TSetElement> TSet<'a, T> for VecSet<'a, T> {
+impl<T: TSetElement> VecSet<T> {
+	/// returns the parent space as a set, None if this is not a subset
+	/// the elements are shared with the parent so no copy is made
+	pub fn parent(&self) -> Option<Self> {
+		self.parent.as_ref().map(|p| Self { set: Rc::clone(p), parent: None })
+	}
+
+	/// returns an iterator over the elements in order
+	pub fn iter(&self) -> std::slice::Iter<'_, T> {
+		self.set.iter()
+	}
+
+	/// returns a new set of f applied to every element
+	/// elements that map to the same value are merged, the result has no parent
+	pub fn map<U: TSetElement>(&self, f: impl Fn(&T) -> U) -> VecSet<U> {
+		self.iter().map(f).collect()
+	}
+}
+
+impl<T: TSetElement> FromIterator<T> for VecSet<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		Self { set: Rc::new(unique(iter.into_iter())), parent: None }
+	}
+}
+
+impl<'b, T> IntoIterator for &'b VecSet<T> {
+	type Item = &'b T;
+	type IntoIter = std::slice::Iter<'b, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.set.iter()
+	}
+}
+
+impl<T: Clone> IntoIterator for VecSet<T> {
+	type Item = T;
+	type IntoIter = std::vec::IntoIter<T>;
+
+	// only copies the elements if they are shared with a subset
+	fn into_iter(self) -> Self::IntoIter {
+		Rc::unwrap_or_clone(self.set).into_iter()
+	}
+}
+
+impl<'a, T: TSetElement> TSet<'a, T> for VecSet<T> {
 
 	fn new() -> Self {
 		Self {
-			set: Vec::new(),
+			set: Rc::new(Vec::new()),
 			parent: None
 		}
 	}
 
 	fn new_from_array(array: &'a[T]) -> Self {
-		Self { set: array.to_vec() , parent: None }
+		Self { set: Rc::new(unique(array.iter().copied())), parent: None }
 	}
 
 	fn subset_from_range(&'a self, start: usize, end: usize) -> Self {
 		Self { 
-			set: self.set[start..end].to_vec(),
-			parent: Some(&self)
+			set: Rc::new(self.set[start..end].to_vec()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+
+	fn subset_from_indices(&'a self, indices: &[usize]) -> Self {
+		Self {
+			set: Rc::new(unique(indices.iter().map(|i| self.set[*i]))),
+			parent: Some(Rc::clone(&self.set))
 		}
 	}
 
@@ -87,18 +279,22 @@ impl<'a, T: TSetElement> TSet<'a, T> for VecSet<'a, T> {
 	}
 
 	fn parent_size(&self) -> Option<usize> {
-		match self.parent {
-			None => None,
-			Some(p) => Some(p.size())
-		}
+		self.parent.as_ref().map(|p| p.len())
+	}
+
+	fn shares_parent(&self, other: &Self) -> bool {
+		// a set with no parent is its own space
+		let a = self.parent.as_ref().unwrap_or(&self.set);
+		let b = other.parent.as_ref().unwrap_or(&other.set);
+		Rc::ptr_eq(a, b) || a == b
 	}
 
 	fn is_subset_of(&self, other: &Self) -> bool {
 		if self.size() > other.size() { return false }
 		
 		let mut start_index = 0;
-		let mut own = self.set.clone();
-		let mut oth = other.set.clone();
+		let mut own = self.set.to_vec();
+		let mut oth = other.set.to_vec();
 
 		own.sort();
 		oth.sort();
@@ -115,105 +311,838 @@ impl<'a, T: TSetElement> TSet<'a, T> for VecSet<'a, T> {
 				}
 			}
 
-			if !found { return false }
-		}
+			if !found { return false }
+		}
+
+		return true
+	}
+
+	fn union(a: &Self, b: &Self) -> Self {
+		// the union of the two sets to return
+		let mut union_set = Vec::new();
+
+		// copy set a into the union
+		for i in a.set.iter() {
+			union_set.push(*i);
+		}
+
+		// sort the union (which is a) and sort a mutable copy of b
+		union_set.sort();
+		let mut b_copy = b.set.to_vec();
+		b_copy.sort();
+
+		// loop through b adding its elements to the union
+		let mut index = 0;
+		for element in b_copy {
+
+			// no need to check a if all elements of a are alrady checked 
+			if index >= a.size() {
+				union_set.push(element);
+			}
+			else {
+				// loop through the union untill the elements are no longer
+				// less than the next element of b
+				while index < a.size() && union_set[index] < element {
+					index += 1;
+				}
+				// if the current union element is not same as the next
+				// element of b then it is greater than it.
+				// add it to the union.
+				// if index is past the len of set a then all remaining
+				// elements of b are not in a
+				if index >= a.size() || union_set[index] != element {
+					union_set.push(element);
+				}
+				else {
+					index += 1;
+				}
+				
+				// if the elements are the same then 
+			}
+		}
+
+		Self { set: Rc::new(union_set), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn intersection(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x.min(y))), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn difference(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x.saturating_sub(y))), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn symmetric_difference(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x.abs_diff(y))), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn complement(&self) -> Option<Self> {
+		let parent = self.parent.as_ref()?;
+		Some(Self { set: Rc::new(merge_counts(parent, &self.set, |x, y| x.saturating_sub(y))), parent: self.parent.clone() })
+	}
+
+	fn contains(&self, element: &T) -> bool {
+		self.set.contains(element)
+	}
+
+	fn is_disjoint(&self, other: &Self) -> bool {
+		merge_counts(&self.set, &other.set, |x, y| x.min(y)).is_empty()
+	}
+
+	fn is_superset_of(&self, other: &Self) -> bool {
+		other.is_subset_of(self)
+	}
+
+	fn filter<F: Fn(&T) -> bool>(&'a self, predicate: F) -> Self {
+		Self {
+			set: Rc::new(self.set.iter().filter(|x| predicate(x)).copied().collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+}
+
+// Walks the sorted elements of a and b together, for each distinct element
+// keep is given how many times it appears in a and in b and returns how many
+// times it should appear in the result
+fn merge_counts<T: TSetElement>(a: &[T], b: &[T], keep: impl Fn(usize, usize) -> usize) -> Vec<T> {
+	let mut own = a.to_vec();
+	let mut oth = b.to_vec();
+	own.sort();
+	oth.sort();
+
+	let mut ret_val = Vec::new();
+	let (mut i, mut j) = (0, 0);
+
+	while i < own.len() || j < oth.len() {
+		// the smallest element not yet looked at in either set
+		let element = match (own.get(i), oth.get(j)) {
+			(Some(x), Some(y)) => *x.min(y),
+			(Some(x), None) => *x,
+			(None, Some(y)) => *y,
+			(None, None) => break
+		};
+
+		let a_count = own[i..].iter().take_while(|x| **x == element).count();
+		let b_count = oth[j..].iter().take_while(|y| **y == element).count();
+		i += a_count;
+		j += b_count;
+
+		for _ in 0..keep(a_count, b_count) {
+			ret_val.push(element);
+		}
+	}
+
+	ret_val
+}
+
+// Keeps the first occurrence of each element in the order they are given
+fn unique<T: TSetElement>(elements: impl Iterator<Item = T>) -> Vec<T> {
+	let mut seen = BTreeSet::new();
+	elements.filter(|element| seen.insert(*element)).collect()
+}
+
+// The parent space of the result of an operation on a and b, a set with no
+// parent is its own space so an operation with one of its events uses theirs
+fn result_parent<P: Clone>(a: &Option<P>, b: &Option<P>) -> Option<P> {
+	a.as_ref().or(b.as_ref()).cloned()
+}
+// endregion:
+
+// MARK: MultiSet
+// region: MultiSet
+/// Implementation of a multiset using vec internally
+///
+/// Elements can repeat and the number of copies of an element is its
+/// multiplicity.  Union keeps the larger multiplicity, intersection the
+/// smaller, and `sum` adds them.
+pub struct MultiSet<T> {
+	set: Rc<Vec<T>>,
+	parent: Option<Rc<Vec<T>>>
+}
+
+// Gives MultiSet the ability to use slices
+impl<T, Idx> std::ops::Index<Idx> for MultiSet<T>
+where Idx: std::slice::SliceIndex<[T]>, {
+	type Output = Idx::Output;
+
+	fn index(&self, index: Idx) -> &Self::Output {
+		&self.set[index]
+	}
+}
+
+impl<T: TSetElement> MultiSet<T> {
+	/// returns the parent space as a multiset, None if this is not a subset
+	/// the elements are shared with the parent so no copy is made
+	pub fn parent(&self) -> Option<Self> {
+		self.parent.as_ref().map(|p| Self { set: Rc::clone(p), parent: None })
+	}
+
+	/// returns the number of copies of element in the multiset
+	pub fn multiplicity(&self, element: &T) -> usize {
+		self.set.iter().filter(|x| *x == element).count()
+	}
+
+	/// returns each distinct element with its multiplicity, in ascending order
+	pub fn counts(&self) -> Vec<(T, usize)> {
+		let mut sorted = self.set.to_vec();
+		sorted.sort();
+
+		let mut ret_val: Vec<(T, usize)> = Vec::new();
+		for element in sorted {
+			match ret_val.last_mut() {
+				Some((last, count)) if *last == element => *count += 1,
+				_ => ret_val.push((element, 1))
+			}
+		}
+		ret_val
+	}
+
+	/// returns a multiset with the multiplicities of a and b added together
+	/// the result shares the parent space of a, or of b if a has none
+	pub fn sum(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x + y)), parent: result_parent(&a.parent, &b.parent) }
+	}
+}
+
+impl<'a, T: TSetElement> TSet<'a, T> for MultiSet<T> {
+
+	fn new() -> Self {
+		Self { set: Rc::new(Vec::new()), parent: None }
+	}
+
+	fn new_from_array(array: &'a[T]) -> Self {
+		Self { set: Rc::new(array.to_vec()), parent: None }
+	}
+
+	fn subset_from_range(&'a self, start: usize, end: usize) -> Self {
+		Self { set: Rc::new(self.set[start..end].to_vec()), parent: Some(Rc::clone(&self.set)) }
+	}
+
+	fn subset_from_indices(&'a self, indices: &[usize]) -> Self {
+		Self { set: Rc::new(indices.iter().map(|i| self.set[*i]).collect()), parent: Some(Rc::clone(&self.set)) }
+	}
+
+	fn size(&self) -> usize {
+		self.set.len()
+	}
+
+	fn is_subset(&self) -> bool {
+		self.parent.is_some()
+	}
+
+	fn parent_size(&self) -> Option<usize> {
+		self.parent.as_ref().map(|p| p.len())
+	}
+
+	fn shares_parent(&self, other: &Self) -> bool {
+		// a set with no parent is its own space
+		let a = self.parent.as_ref().unwrap_or(&self.set);
+		let b = other.parent.as_ref().unwrap_or(&other.set);
+		Rc::ptr_eq(a, b) || a == b
+	}
+
+	fn is_subset_of(&self, other: &Self) -> bool {
+		merge_counts(&self.set, &other.set, |x, y| x.saturating_sub(y)).is_empty()
+	}
+
+	fn union(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x.max(y))), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn intersection(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x.min(y))), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn difference(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x.saturating_sub(y))), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn symmetric_difference(a: &Self, b: &Self) -> Self {
+		Self { set: Rc::new(merge_counts(&a.set, &b.set, |x, y| x.abs_diff(y))), parent: result_parent(&a.parent, &b.parent) }
+	}
+
+	fn complement(&self) -> Option<Self> {
+		let parent = self.parent.as_ref()?;
+		Some(Self { set: Rc::new(merge_counts(parent, &self.set, |x, y| x.saturating_sub(y))), parent: self.parent.clone() })
+	}
+
+	fn contains(&self, element: &T) -> bool {
+		self.set.contains(element)
+	}
+
+	fn is_disjoint(&self, other: &Self) -> bool {
+		merge_counts(&self.set, &other.set, |x, y| x.min(y)).is_empty()
+	}
+
+	fn is_superset_of(&self, other: &Self) -> bool {
+		other.is_subset_of(self)
+	}
+
+	fn filter<F: Fn(&T) -> bool>(&'a self, predicate: F) -> Self {
+		Self {
+			set: Rc::new(self.set.iter().filter(|x| predicate(x)).copied().collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+}
+// endregion:
+
+// MARK: Set
+// region: ArraySet
+/// This is a repensentation of a set theory set
+///
+/// The elements are borrowed from a slice, so building a set from a unique
+/// array or taking a range of it does not allocate.  Results that are not a
+/// piece of an existing slice, like a union, own their elements.
+pub struct ArraySet<'a, T: Clone> {
+	parent_space: Option<&'a [T]>,
+	set: Cow<'a, [T]>
+}
+
+// Gives Set the ability to use slices
+impl<'a, T: Clone, Idx> std::ops::Index<Idx> for ArraySet<'a, T>
+where
+	Idx: std::slice::SliceIndex<[T]>, {
+		type Output = Idx::Output;
+
+		fn index(&self, index: Idx) -> &Self::Output {
+			&self.set[index]
+		}
+	}
+
+impl<'a, T: TSetElement> TSet<'a, T> for ArraySet<'a, T> {
+
+	fn new() -> Self {
+		Self { parent_space: None, set: Cow::Borrowed(&[]) }
+	}
+
+	fn new_from_array(array: &'a [T]) -> Self {
+		// only copy the array when there are repeats to drop
+		let mut seen = BTreeSet::new();
+		let is_unique = array.iter().all(|x| seen.insert(*x));
+		let set = if is_unique { Cow::Borrowed(array) } else { Cow::Owned(unique(array.iter().copied())) };
+
+		Self { parent_space: None, set }
+	}
+
+	fn subset_from_range(&'a self, start: usize, end: usize) -> Self {
+		Self { parent_space: Some(&self.set), set: Cow::Borrowed(&self.set[start..end]) }
+	}
+
+	fn subset_from_indices(&'a self, indices: &[usize]) -> Self {
+		Self {
+			parent_space: Some(&self.set),
+			set: Cow::Owned(unique(indices.iter().map(|i| self.set[*i])))
+		}
+	}
+
+	fn size(&self) -> usize {
+		self.set.len()
+	}
+
+	fn is_subset(&self) -> bool {
+		self.parent_space.is_some()
+	}
+
+	fn parent_size(&self) -> Option<usize> {
+		self.parent_space.map(|parent| parent.len())
+	}
+
+	fn shares_parent(&self, other: &Self) -> bool {
+		// a set with no parent is its own space
+		let a = self.parent_space.unwrap_or(&self.set);
+		let b = other.parent_space.unwrap_or(&other.set);
+		std::ptr::eq(a, b) || a == b
+	}
+
+	fn is_subset_of(&self, other: &Self) -> bool {
+		merge_counts(&self.set, &other.set, |x, y| x.saturating_sub(y)).is_empty()
+	}
+
+	fn union(a: &Self, b: &Self) -> Self {
+		Self { parent_space: a.parent_space.or(b.parent_space), set: Cow::Owned(merge_counts(&a.set, &b.set, |x, y| x.max(y))) }
+	}
+
+	fn intersection(a: &Self, b: &Self) -> Self {
+		Self { parent_space: a.parent_space.or(b.parent_space), set: Cow::Owned(merge_counts(&a.set, &b.set, |x, y| x.min(y))) }
+	}
+
+	fn difference(a: &Self, b: &Self) -> Self {
+		Self { parent_space: a.parent_space.or(b.parent_space), set: Cow::Owned(merge_counts(&a.set, &b.set, |x, y| x.saturating_sub(y))) }
+	}
+
+	fn symmetric_difference(a: &Self, b: &Self) -> Self {
+		Self { parent_space: a.parent_space.or(b.parent_space), set: Cow::Owned(merge_counts(&a.set, &b.set, |x, y| x.abs_diff(y))) }
+	}
+
+	fn complement(&self) -> Option<Self> {
+		let parent = self.parent_space?;
+		Some(Self { parent_space: self.parent_space, set: Cow::Owned(merge_counts(parent, &self.set, |x, y| x.saturating_sub(y))) })
+	}
+
+	fn contains(&self, element: &T) -> bool {
+		self.set.contains(element)
+	}
+
+	fn is_disjoint(&self, other: &Self) -> bool {
+		merge_counts(&self.set, &other.set, |x, y| x.min(y)).is_empty()
+	}
+
+	fn is_superset_of(&self, other: &Self) -> bool {
+		other.is_subset_of(self)
+	}
+
+	fn filter<F: Fn(&T) -> bool>(&'a self, predicate: F) -> Self {
+		Self {
+			parent_space: Some(&self.set),
+			set: Cow::Owned(self.set.iter().filter(|x| predicate(x)).copied().collect())
+		}
+	}
+}
+// endregion:
+
+
+// MARK: HashedSet
+// region: HashedSet
+/// Implementation of a set using a std HashSet internally
+///
+/// Elements only need to be `Clone + Eq + Hash` so types like String can be
+/// used, and membership, union and the other set operations are linear
+/// instead of sorting on every call.  Positions used by `subset_from_range`
+/// and `subset_from_indices` follow the iteration order of the set.
+pub struct HashedSet<T> {
+	set: Rc<HashSet<T>>,
+	parent: Option<Rc<HashSet<T>>>
+}
+
+impl<T: Clone + Eq + Hash> HashedSet<T> {
+	/// returns the parent space as a set, None if this is not a subset
+	pub fn parent(&self) -> Option<Self> {
+		self.parent.as_ref().map(|p| Self { set: Rc::clone(p), parent: None })
+	}
+
+	/// returns an iterator over the elements in no particular order
+	pub fn iter(&self) -> impl Iterator<Item = &T> {
+		self.set.iter()
+	}
+
+	fn with_parent(set: HashSet<T>, parent: &Option<Rc<HashSet<T>>>) -> Self {
+		Self { set: Rc::new(set), parent: parent.clone() }
+	}
+}
+
+impl<T> From<HashSet<T>> for HashedSet<T> {
+	fn from(set: HashSet<T>) -> Self {
+		Self { set: Rc::new(set), parent: None }
+	}
+}
+
+impl<'a, T: Clone + Eq + Hash> TSet<'a, T> for HashedSet<T> {
+
+	fn new() -> Self {
+		Self { set: Rc::new(HashSet::new()), parent: None }
+	}
+
+	fn new_from_array(array: &'a[T]) -> Self {
+		Self { set: Rc::new(array.iter().cloned().collect()), parent: None }
+	}
+
+	fn subset_from_range(&'a self, start: usize, end: usize) -> Self {
+		Self {
+			set: Rc::new(self.set.iter().skip(start).take(end - start).cloned().collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+
+	fn subset_from_indices(&'a self, indices: &[usize]) -> Self {
+		let elements: Vec<&T> = self.set.iter().collect();
+		Self {
+			set: Rc::new(indices.iter().map(|i| elements[*i].clone()).collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+
+	fn size(&self) -> usize {
+		self.set.len()
+	}
+
+	fn is_subset(&self) -> bool {
+		self.parent.is_some()
+	}
+
+	fn parent_size(&self) -> Option<usize> {
+		self.parent.as_ref().map(|p| p.len())
+	}
+
+	fn shares_parent(&self, other: &Self) -> bool {
+		// a set with no parent is its own space
+		let a = self.parent.as_ref().unwrap_or(&self.set);
+		let b = other.parent.as_ref().unwrap_or(&other.set);
+		Rc::ptr_eq(a, b) || a == b
+	}
+
+	fn is_subset_of(&self, other: &Self) -> bool {
+		self.set.is_subset(&other.set)
+	}
+
+	fn union(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.union(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn intersection(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.intersection(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn difference(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.difference(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn symmetric_difference(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.symmetric_difference(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn complement(&self) -> Option<Self> {
+		let parent = self.parent.as_ref()?;
+		Some(Self::with_parent(parent.difference(&self.set).cloned().collect(), &self.parent))
+	}
+
+	fn contains(&self, element: &T) -> bool {
+		self.set.contains(element)
+	}
+
+	fn is_disjoint(&self, other: &Self) -> bool {
+		self.set.is_disjoint(&other.set)
+	}
+
+	fn is_superset_of(&self, other: &Self) -> bool {
+		self.set.is_superset(&other.set)
+	}
+
+	fn filter<F: Fn(&T) -> bool>(&'a self, predicate: F) -> Self {
+		Self {
+			set: Rc::new(self.set.iter().filter(|x| predicate(x)).cloned().collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+}
+// endregion:
+
+// MARK: TreeSet
+// region: TreeSet
+/// Implementation of a set using a std BTreeSet internally
+///
+/// Elements only need to be `Clone + Ord` so types like String can be used.
+/// The set is kept sorted, so positions used by `subset_from_range` and
+/// `subset_from_indices` are positions in ascending order.
+pub struct TreeSet<T> {
+	set: Rc<BTreeSet<T>>,
+	parent: Option<Rc<BTreeSet<T>>>
+}
+
+impl<T: Clone + Ord> TreeSet<T> {
+	/// returns the parent space as a set, None if this is not a subset
+	pub fn parent(&self) -> Option<Self> {
+		self.parent.as_ref().map(|p| Self { set: Rc::clone(p), parent: None })
+	}
+
+	/// returns an iterator over the elements in ascending order
+	pub fn iter(&self) -> impl Iterator<Item = &T> {
+		self.set.iter()
+	}
+
+	fn with_parent(set: BTreeSet<T>, parent: &Option<Rc<BTreeSet<T>>>) -> Self {
+		Self { set: Rc::new(set), parent: parent.clone() }
+	}
+}
+
+impl<T> From<BTreeSet<T>> for TreeSet<T> {
+	fn from(set: BTreeSet<T>) -> Self {
+		Self { set: Rc::new(set), parent: None }
+	}
+}
+
+impl<'a, T: Clone + Ord> TSet<'a, T> for TreeSet<T> {
+
+	fn new() -> Self {
+		Self { set: Rc::new(BTreeSet::new()), parent: None }
+	}
+
+	fn new_from_array(array: &'a[T]) -> Self {
+		Self { set: Rc::new(array.iter().cloned().collect()), parent: None }
+	}
+
+	fn subset_from_range(&'a self, start: usize, end: usize) -> Self {
+		Self {
+			set: Rc::new(self.set.iter().skip(start).take(end - start).cloned().collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+
+	fn subset_from_indices(&'a self, indices: &[usize]) -> Self {
+		let elements: Vec<&T> = self.set.iter().collect();
+		Self {
+			set: Rc::new(indices.iter().map(|i| elements[*i].clone()).collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+
+	fn size(&self) -> usize {
+		self.set.len()
+	}
+
+	fn is_subset(&self) -> bool {
+		self.parent.is_some()
+	}
+
+	fn parent_size(&self) -> Option<usize> {
+		self.parent.as_ref().map(|p| p.len())
+	}
+
+	fn shares_parent(&self, other: &Self) -> bool {
+		// a set with no parent is its own space
+		let a = self.parent.as_ref().unwrap_or(&self.set);
+		let b = other.parent.as_ref().unwrap_or(&other.set);
+		Rc::ptr_eq(a, b) || a == b
+	}
+
+	fn is_subset_of(&self, other: &Self) -> bool {
+		self.set.is_subset(&other.set)
+	}
+
+	fn union(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.union(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn intersection(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.intersection(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn difference(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.difference(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn symmetric_difference(a: &Self, b: &Self) -> Self {
+		Self::with_parent(a.set.symmetric_difference(&b.set).cloned().collect(), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn complement(&self) -> Option<Self> {
+		let parent = self.parent.as_ref()?;
+		Some(Self::with_parent(parent.difference(&self.set).cloned().collect(), &self.parent))
+	}
+
+	fn contains(&self, element: &T) -> bool {
+		self.set.contains(element)
+	}
+
+	fn is_disjoint(&self, other: &Self) -> bool {
+		self.set.is_disjoint(&other.set)
+	}
+
+	fn is_superset_of(&self, other: &Self) -> bool {
+		self.set.is_superset(&other.set)
+	}
+
+	fn filter<F: Fn(&T) -> bool>(&'a self, predicate: F) -> Self {
+		Self {
+			set: Rc::new(self.set.iter().filter(|x| predicate(x)).cloned().collect()),
+			parent: Some(Rc::clone(&self.set))
+		}
+	}
+}
+// endregion:
+
+
+// MARK: WeightedSet
+// region: WeightedSet
+/// A probubility mass that can be carried by the elements of a WeightedSet
+pub trait TWeight: Clone + PartialEq {
+	/// the total weight of an empty set
+	fn zero() -> Self;
+
+	/// the weight given to every element when none is given
+	fn one() -> Self;
+
+	/// returns the sum of two weights
+	fn plus(&self, other: &Self) -> Self;
+
+	/// returns true if the weight is negative, or otherwise can not be a mass
+	fn is_negative(&self) -> bool;
+
+	/// returns this weight divided by total as an f64, NaN if total is zero
+	fn ratio(&self, total: &Self) -> f64;
+}
+
+impl TWeight for f64 {
+	fn zero() -> Self { 0.0 }
+
+	fn one() -> Self { 1.0 }
+
+	fn plus(&self, other: &Self) -> Self { self + other }
+
+	fn is_negative(&self) -> bool { self.is_nan() || *self < 0.0 }
+
+	fn ratio(&self, total: &Self) -> f64 { self / total }
+}
+
+// the division is exact, only the result is rounded
+impl TWeight for Rational {
+	fn zero() -> Self { Rational::from(0) }
+
+	fn one() -> Self { Rational::from(1) }
+
+	fn plus(&self, other: &Self) -> Self { self + other }
+
+	fn is_negative(&self) -> bool { self.signum() < 0 }
+
+	fn ratio(&self, total: &Self) -> f64 {
+		if total.is_zero() { return f64::NAN }
+		(self / total).to_f64()
+	}
+}
+
+/// Implementation of a set where every element carries a weight
+///
+/// The weights do not need to add to one, the probubility of a subset is its
+/// total weight divided by the total weight of its parent space.  Elements
+/// are unique like a VecSet and keep the weight they had in the parent.
+pub struct WeightedSet<T, W> {
+	set: Rc<Vec<(T, W)>>,
+	parent: Option<Rc<Vec<(T, W)>>>
+}
+
+impl<T: TSetElement, W: TWeight> WeightedSet<T, W> {
+	/// returns a new set with a weight for each element
+	/// returns None if the lengths differ, an element repeats or a weight is negative
+	pub fn new_weighted(elements: &[T], weights: &[W]) -> Option<Self> {
+		if elements.len() != weights.len() { return None }
+		if unique(elements.iter().copied()).len() != elements.len() { return None }
+		if weights.iter().any(|w| w.is_negative()) { return None }
+
+		let set = elements.iter().copied().zip(weights.iter().cloned()).collect();
+		Some(Self { set: Rc::new(set), parent: None })
+	}
+
+	/// returns the weight of element, None if it is not in the set
+	pub fn weight(&self, element: &T) -> Option<W> {
+		self.set.iter().find(|(x, _)| x == element).map(|(_, w)| w.clone())
+	}
+
+	/// returns the total weight of the set
+	pub fn mass(&self) -> W {
+		total_weight(&self.set)
+	}
+
+	/// returns the total weight of the parent space, None if this is not a subset
+	pub fn parent_mass(&self) -> Option<W> {
+		self.parent.as_ref().map(|p| total_weight(p))
+	}
+
+	/// returns an iterator over the elements and their weights
+	pub fn iter(&self) -> impl Iterator<Item = &(T, W)> {
+		self.set.iter()
+	}
+
+	fn with_parent(set: Vec<(T, W)>, parent: &Option<Rc<Vec<(T, W)>>>) -> Self {
+		Self { set: Rc::new(set), parent: parent.clone() }
+	}
+
+	// the elements of a with (keep) or without (!keep) a match in b
+	fn select(a: &[(T, W)], b: &[(T, W)], keep: bool) -> Vec<(T, W)> {
+		a.iter().filter(|(x, _)| b.iter().any(|(y, _)| x == y) == keep).cloned().collect()
+	}
+}
+
+fn total_weight<T, W: TWeight>(set: &[(T, W)]) -> W {
+	set.iter().fold(W::zero(), |acc, (_, w)| acc.plus(w))
+}
 
-		return true
+impl<'a, T: TSetElement, W: TWeight> TSet<'a, T> for WeightedSet<T, W> {
+
+	fn new() -> Self {
+		Self { set: Rc::new(Vec::new()), parent: None }
 	}
 
-	fn union(a: &Self, b: &Self) -> Self {
-		// the union of the two sets to return
-		let mut union_set = Self::new();
+	fn new_from_array(array: &'a[T]) -> Self {
+		let set = unique(array.iter().copied()).into_iter().map(|x| (x, W::one())).collect();
+		Self { set: Rc::new(set), parent: None }
+	}
 
-		// copy set a into the union
-		for i in a.set.iter() {
-			union_set.set.push(*i);
+	fn subset_from_range(&'a self, start: usize, end: usize) -> Self {
+		Self { set: Rc::new(self.set[start..end].to_vec()), parent: Some(Rc::clone(&self.set)) }
+	}
+
+	fn subset_from_indices(&'a self, indices: &[usize]) -> Self {
+		let mut set: Vec<(T, W)> = Vec::new();
+		for i in indices {
+			if !set.iter().any(|(x, _)| *x == self.set[*i].0) { set.push(self.set[*i].clone()); }
 		}
+		Self { set: Rc::new(set), parent: Some(Rc::clone(&self.set)) }
+	}
 
-		// sort the union (which is a) and sort a mutable copy of b
-		union_set.set.sort();
-		let mut b_copy = b.set.clone();
-		b_copy.sort();
+	fn size(&self) -> usize {
+		self.set.len()
+	}
 
-		// loop through b adding its elements to the union
-		let mut index = 0;
-		for element in b_copy {
+	fn is_subset(&self) -> bool {
+		self.parent.is_some()
+	}
 
-			// no need to check a if all elements of a are alrady checked 
-			if index >= a.size() {
-				union_set.set.push(element);
-			}
-			else {
-				// loop through the union untill the elements are no longer
-				// less than the next element of b
-				while index < a.size() && union_set[index] < element {
-					index += 1;
-				}
-				// if the current union element is not same as the next
-				// element of b then it is greater than it.
-				// add it to the union.
-				// if index is past the len of set a then all remaining
-				// elements of b are not in a
-				if index >= a.size() || union_set[index] != element {
-					union_set.set.push(element);
-				}
-				else {
-					index += 1;
-				}
-				
-				// if the elements are the same then 
-			}
-		}
+	fn parent_size(&self) -> Option<usize> {
+		self.parent.as_ref().map(|p| p.len())
+	}
 
-		return union_set
+	fn shares_parent(&self, other: &Self) -> bool {
+		// a set with no parent is its own space
+		let a = self.parent.as_ref().unwrap_or(&self.set);
+		let b = other.parent.as_ref().unwrap_or(&other.set);
+		Rc::ptr_eq(a, b) || a == b
 	}
-}
-// endregion:
 
-// MARK: Set
-// region: ArraySet
-/// This is a repensentation of a set theory set
-pub struct ArraySet<'a, T> {
-	parent_space: Option<&'a [T]>,
-	set: &'a [T]
-}
+	fn is_subset_of(&self, other: &Self) -> bool {
+		Self::select(&self.set, &other.set, false).is_empty()
+	}
 
-// Gives Set the ability to use slices
-impl<'a, T, Idx> std::ops::Index<Idx> for ArraySet<'a, T>
-where
-	Idx: std::slice::SliceIndex<[T]>, {
-		type Output = Idx::Output;
+	fn union(a: &Self, b: &Self) -> Self {
+		let mut set = a.set.to_vec();
+		set.extend(Self::select(&b.set, &a.set, false));
+		Self::with_parent(set, &result_parent(&a.parent, &b.parent))
+	}
 
-		fn index(&self, index: Idx) -> &Self::Output {
-			&self.set[index]
-		}
+	fn intersection(a: &Self, b: &Self) -> Self {
+		Self::with_parent(Self::select(&a.set, &b.set, true), &result_parent(&a.parent, &b.parent))
+	}
+
+	fn difference(a: &Self, b: &Self) -> Self {
+		Self::with_parent(Self::select(&a.set, &b.set, false), &result_parent(&a.parent, &b.parent))
 	}
 
-impl<'a, T> ArraySet<'a, T> {
-	/// Incomplete
-	pub fn new_from_array(array: &'a [T]) -> Self {
-		Self {parent_space: None, set: array}
+	fn symmetric_difference(a: &Self, b: &Self) -> Self {
+		let mut set = Self::select(&a.set, &b.set, false);
+		set.extend(Self::select(&b.set, &a.set, false));
+		Self::with_parent(set, &result_parent(&a.parent, &b.parent))
 	}
-	/// Incomplete
-	pub fn subset_from_range(&self, start: usize, end: usize) -> Self {
-		Self { parent_space: Some(self.set), set: &self.set[start..end]}
+
+	fn complement(&self) -> Option<Self> {
+		let parent = self.parent.as_ref()?;
+		Some(Self::with_parent(Self::select(parent, &self.set, false), &self.parent))
 	}
-	/// Incomplete
-	pub fn size(&self) -> usize {
-		self.set.len()
+
+	fn contains(&self, element: &T) -> bool {
+		self.set.iter().any(|(x, _)| x == element)
 	}
-	/// Incomplete
-	pub fn is_subset(&self) -> bool {
-		match self.parent_space {
-			None => return false,
-			_ => return true
-		}
+
+	fn is_disjoint(&self, other: &Self) -> bool {
+		Self::select(&self.set, &other.set, true).is_empty()
 	}
-	/// Incomplete
-	pub fn parent_size(&self) -> Option<usize> {
-		match self.parent_space {
-			None => return None,
-			Some(parent) => return Some(parent.len())
+
+	fn is_superset_of(&self, other: &Self) -> bool {
+		other.is_subset_of(self)
+	}
+
+	fn filter<F: Fn(&T) -> bool>(&'a self, predicate: F) -> Self {
+		Self {
+			set: Rc::new(self.set.iter().filter(|(x, _)| predicate(x)).cloned().collect()),
+			parent: Some(Rc::clone(&self.set))
 		}
 	}
 }
@@ -229,12 +1158,26 @@ mod tests {
 // region: test_new_from_array
 	fn test_new_from_array(value: &[i32]) {
 		let set = VecSet::new_from_array(value);
-		assert_eq!(set.set, value);
+		assert_eq!(*set.set, value);
 		assert_eq!(set.is_subset(), false);
 	}
 
 	#[test] fn test_new_from_array_0() { test_new_from_array(&[1,2,3,4,5]); }
 	#[test] fn test_new_from_array_1() { test_new_from_array(&[0; 0]); }
+
+	#[test] fn test_new_from_array_unique() {
+		let set = VecSet::new_from_array(&[3,1,3,2,1]);
+		assert_eq!(*set.set, &[3,1,2]);
+		assert_eq!(set.size(), 3);
+	}
+
+	#[test] fn test_new_from_array_unique_large() {
+		// every value appears twice, far too many to compare each pair
+		let values: Vec<u32> = (0..200_000).map(|i| (i * 7919) % 100_000).collect();
+		let set = VecSet::new_from_array(&values);
+		assert_eq!(set.size(), 100_000);
+		assert_eq!(*set.set, values[..100_000]);
+	}
 // endregion:
 	
 // region: test_subset_from_range
@@ -245,7 +1188,7 @@ mod tests {
 		expected: &[i32]
 	) {
 		let subset = set.subset_from_range(start, end);
-		assert_eq!(subset.set, expected);
+		assert_eq!(*subset.set, expected);
 		assert_eq!(subset.is_subset(), true);
 	}
 
@@ -264,6 +1207,36 @@ mod tests {
 	);}
 // endregion:
 	
+// region: test_subset_from_indices
+	fn test_subset_from_indices(
+		set: &VecSet<i32>,
+		indices: &[usize],
+		expected: &[i32]
+	) {
+		let subset = set.subset_from_indices(indices);
+		assert_eq!(*subset.set, expected);
+		assert_eq!(subset.parent_size(), Some(set.size()));
+	}
+
+	#[test] fn test_subset_from_indices_0() { test_subset_from_indices(
+		&VecSet::new_from_array(&[1,2,3,4,5,6,7]),
+		&[0,2,6],
+		&[1,3,7]
+	);}
+
+	#[test] fn test_subset_from_indices_1() { test_subset_from_indices(
+		&VecSet::new_from_array(&[1,2,3,4,5,6,7]),
+		&[4,1],
+		&[5,2]
+	);}
+
+	#[test] fn test_subset_from_indices_2() { test_subset_from_indices(
+		&VecSet::new_from_array(&[1,2,3]),
+		&[0; 0],
+		&[0; 0]
+	);}
+// endregion:
+
 // region: test_is_subset
 	fn test_is_subset(set: &VecSet<i32>, expected: bool) {
 		assert_eq!(set.is_subset(), expected)
@@ -352,9 +1325,9 @@ mod tests {
 
 // region: test_union
 	fn test_union(a: VecSet<i32>, b: VecSet<i32>, expected: VecSet<i32>) {
-		let mut union = VecSet::union(&a, &b);
-		union.set.sort();
-		assert_eq!(union.set, expected.set);
+		let mut union = VecSet::union(&a, &b).set.to_vec();
+		union.sort();
+		assert_eq!(union, *expected.set);
 	}
 
 	#[test] fn test_union_0() { test_union(
@@ -366,25 +1339,25 @@ mod tests {
 	#[test] fn test_union_1() { test_union(
 		VecSet::new_from_array(&[1,2,3]), 
 		VecSet::new_from_array(&[3,3,4,5]), 
-		VecSet::new_from_array(&[1,2,3,3,4,5])
+		VecSet::new_from_array(&[1,2,3,4,5])
 	);}
 
 	#[test] fn test_union_2() { test_union(
 		VecSet::new_from_array(&[3,2,1]), 
 		VecSet::new_from_array(&[3,3,4,5]), 
-		VecSet::new_from_array(&[1,2,3,3,4,5])
+		VecSet::new_from_array(&[1,2,3,4,5])
 	);}
 
 	#[test] fn test_union_3() { test_union(
 		VecSet::new_from_array(&[3,2,3,1]), 
 		VecSet::new_from_array(&[3,3,3,4,5]), 
-		VecSet::new_from_array(&[1,2,3,3,3,4,5])
+		VecSet::new_from_array(&[1,2,3,4,5])
 	);}
 
 	#[test] fn test_union_4() { test_union(
 		VecSet::new_from_array(&[0;0]), 
 		VecSet::new_from_array(&[3,3,3,4,5]), 
-		VecSet::new_from_array(&[3,3,3,4,5])
+		VecSet::new_from_array(&[3,4,5])
 	);}
 
 	#[test] fn test_union_5() { test_union(
@@ -400,4 +1373,486 @@ mod tests {
 	);}
 // endregion:
 
-}
\ No newline at end of file
+// region: test_parent
+	// the sample space is dropped at the end of this function
+	fn subset_of_local_space() -> VecSet<i32> {
+		let space = VecSet::new_from_array(&[1,2,3,4,5]);
+		space.subset_from_range(1, 3)
+	}
+
+	#[test] fn test_parent_outlives_scope() {
+		let subset = subset_of_local_space();
+		assert_eq!(*subset.set, &[2,3]);
+		assert_eq!(subset.parent_size(), Some(5));
+		assert_eq!(subset.parent().map(|p| p.set.to_vec()), Some(vec![1,2,3,4,5]));
+		assert_eq!(*subset.complement().unwrap().set, &[1,4,5]);
+	}
+
+	#[test] fn test_parent_is_shared() {
+		let space = VecSet::new_from_array(&[1,2,3]);
+		let a = space.subset_from_indices(&[0]);
+		let b = space.subset_from_indices(&[1,2]);
+		assert!(Rc::ptr_eq(a.parent.as_ref().unwrap(), &space.set));
+		assert!(Rc::ptr_eq(a.parent.as_ref().unwrap(), b.parent.as_ref().unwrap()));
+		assert!(VecSet::new_from_array(&[1]).parent().is_none());
+	}
+// endregion:
+
+// region: test_shares_parent
+	#[test] fn test_shares_parent() {
+		let space = VecSet::new_from_array(&[1,2,3,4]);
+		let copy = VecSet::new_from_array(&[1,2,3,4]);
+		let other = VecSet::new_from_array(&[1,2,3]);
+
+		assert!(space.subset_from_range(0, 1).shares_parent(&space.filter(|x| *x > 2)));
+		assert!(space.subset_from_range(0, 1).shares_parent(&copy.subset_from_range(1, 2)));
+		assert!(!space.subset_from_range(0, 1).shares_parent(&other.subset_from_range(0, 1)));
+		assert!(space.shares_parent(&space.subset_from_range(0, 1)));
+		assert!(space.shares_parent(&copy));
+		assert!(!space.shares_parent(&other.subset_from_range(0, 1)));
+	}
+
+	#[test] fn test_operation_with_own_space() {
+		let space = VecSet::new_from_array(&[1,2,3,4]);
+		let event = space.filter(|x| *x > 2);
+
+		let result = VecSet::intersection(&space, &event);
+		assert_eq!(*result.set, [3,4]);
+		assert!(Rc::ptr_eq(result.parent.as_ref().unwrap(), &space.set));
+		assert!(VecSet::union(&space, &space).parent.is_none());
+	}
+// endregion:
+
+// region: test_intersection
+	fn test_intersection(a: VecSet<i32>, b: VecSet<i32>, expected: &[i32]) {
+		assert_eq!(*VecSet::intersection(&a, &b).set, expected);
+	}
+
+	#[test] fn test_intersection_0() { test_intersection(
+		VecSet::new_from_array(&[1,2,3,4]),
+		VecSet::new_from_array(&[3,4,5]),
+		&[3,4]
+	);}
+
+	#[test] fn test_intersection_1() { test_intersection(
+		VecSet::new_from_array(&[3,3,1,2]),
+		VecSet::new_from_array(&[3,3,3,5]),
+		&[3]
+	);}
+
+	#[test] fn test_intersection_2() { test_intersection(
+		VecSet::new_from_array(&[1,2]),
+		VecSet::new_from_array(&[0;0]),
+		&[0;0]
+	);}
+// endregion:
+
+// region: test_difference
+	fn test_difference(a: VecSet<i32>, b: VecSet<i32>, expected: &[i32]) {
+		assert_eq!(*VecSet::difference(&a, &b).set, expected);
+	}
+
+	#[test] fn test_difference_0() { test_difference(
+		VecSet::new_from_array(&[4,3,2,1]),
+		VecSet::new_from_array(&[3,4,5]),
+		&[1,2]
+	);}
+
+	#[test] fn test_difference_1() { test_difference(
+		VecSet::new_from_array(&[3,3,3,1]),
+		VecSet::new_from_array(&[3]),
+		&[1]
+	);}
+
+	#[test] fn test_difference_2() { test_difference(
+		VecSet::new_from_array(&[1,2]),
+		VecSet::new_from_array(&[1,2,3]),
+		&[0;0]
+	);}
+// endregion:
+
+// region: test_symmetric_difference
+	fn test_symmetric_difference(a: VecSet<i32>, b: VecSet<i32>, expected: &[i32]) {
+		assert_eq!(*VecSet::symmetric_difference(&a, &b).set, expected);
+	}
+
+	#[test] fn test_symmetric_difference_0() { test_symmetric_difference(
+		VecSet::new_from_array(&[1,2,3,4]),
+		VecSet::new_from_array(&[3,4,5]),
+		&[1,2,5]
+	);}
+
+	#[test] fn test_symmetric_difference_1() { test_symmetric_difference(
+		VecSet::new_from_array(&[1,2]),
+		VecSet::new_from_array(&[2,1]),
+		&[0;0]
+	);}
+// endregion:
+
+// region: test_complement
+	fn test_complement(subset: &VecSet<i32>, expected: Option<&[i32]>) {
+		let complement = subset.complement();
+		assert_eq!(complement.as_ref().map(|c| c.set.as_slice()), expected);
+		if let Some(c) = complement {
+			assert_eq!(c.parent_size(), subset.parent_size());
+		}
+	}
+
+	#[test] fn test_complement_0() { test_complement(
+		&VecSet::new_from_array(&[1,2,3,4,5]).subset_from_indices(&[3,0]),
+		Some(&[2,3,5])
+	);}
+
+	#[test] fn test_complement_1() { test_complement(
+		&VecSet::new_from_array(&[1,2,3]).subset_from_range(0, 3),
+		Some(&[0;0])
+	);}
+
+	#[test] fn test_complement_2() { test_complement(
+		&VecSet::new_from_array(&[1,2,3]),
+		None
+	);}
+// endregion:
+
+// region: test_contains
+	#[test] fn test_contains() {
+		let set = VecSet::new_from_array(&[5,1,3]);
+		assert!(set.contains(&3));
+		assert!(!set.contains(&2));
+		assert!(!VecSet::new_from_array(&[0;0]).contains(&0));
+	}
+// endregion:
+
+// region: test_is_disjoint
+	fn test_is_disjoint(a: VecSet<i32>, b: VecSet<i32>, expected: bool) {
+		assert_eq!(a.is_disjoint(&b), expected)
+	}
+
+	#[test] fn test_is_disjoint_0() { test_is_disjoint(
+		VecSet::new_from_array(&[1,2,3]),
+		VecSet::new_from_array(&[4,5]),
+		true
+	);}
+
+	#[test] fn test_is_disjoint_1() { test_is_disjoint(
+		VecSet::new_from_array(&[1,2,3]),
+		VecSet::new_from_array(&[5,3]),
+		false
+	);}
+
+	#[test] fn test_is_disjoint_2() { test_is_disjoint(
+		VecSet::new_from_array(&[0;0]),
+		VecSet::new_from_array(&[0;0]),
+		true
+	);}
+
+	#[test] fn test_is_disjoint_array_set() {
+		assert!(ArraySet::new_from_array(&[3,1,2]).is_disjoint(&ArraySet::new_from_array(&[5,4])));
+		assert!(!ArraySet::new_from_array(&[3,1,2]).is_disjoint(&ArraySet::new_from_array(&[5,2])));
+	}
+// endregion:
+
+// region: test_is_superset_of
+	fn test_is_superset_of(set: VecSet<i32>, other: VecSet<i32>, expected: bool) {
+		assert_eq!(set.is_superset_of(&other), expected)
+	}
+
+	#[test] fn test_is_superset_of_0() { test_is_superset_of(
+		VecSet::new_from_array(&[1,2,3,4,5,6,7]),
+		VecSet::new_from_array(&[3,2,6]),
+		true
+	);}
+
+	#[test] fn test_is_superset_of_1() { test_is_superset_of(
+		VecSet::new_from_array(&[3,2,6]),
+		VecSet::new_from_array(&[1,2,3,4,5,6,7]),
+		false
+	);}
+// endregion:
+
+// region: test_multiset
+	#[test] fn test_multiset_keeps_repeats() {
+		let set = MultiSet::new_from_array(&[3,1,3,2,3]);
+		assert_eq!(set.size(), 5);
+		assert_eq!(set.multiplicity(&3), 3);
+		assert_eq!(set.multiplicity(&4), 0);
+		assert_eq!(set.counts(), &[(1,1), (2,1), (3,3)]);
+	}
+
+	fn test_multiset_op(
+		op: fn(&MultiSet<i32>, &MultiSet<i32>) -> MultiSet<i32>,
+		a: &[i32],
+		b: &[i32],
+		expected: &[i32]
+	) {
+		let result = op(&MultiSet::new_from_array(a), &MultiSet::new_from_array(b));
+		assert_eq!(*result.set, expected);
+	}
+
+	#[test] fn test_multiset_union() {
+		test_multiset_op(MultiSet::union, &[1,2,3], &[3,3,4,5], &[1,2,3,3,4,5]);
+	}
+
+	#[test] fn test_multiset_intersection() {
+		test_multiset_op(MultiSet::intersection, &[3,3,1,2], &[3,3,3,5], &[3,3]);
+	}
+
+	#[test] fn test_multiset_sum() {
+		test_multiset_op(MultiSet::sum, &[1,3], &[3,3,4], &[1,3,3,3,4]);
+	}
+
+	#[test] fn test_multiset_difference() {
+		test_multiset_op(MultiSet::difference, &[3,3,3,1], &[3], &[1,3,3]);
+	}
+
+	#[test] fn test_multiset_symmetric_difference() {
+		test_multiset_op(MultiSet::symmetric_difference, &[1,1,2], &[1,2,2,2], &[1,2,2]);
+	}
+
+	#[test] fn test_multiset_is_subset_of() {
+		let a = MultiSet::new_from_array(&[2,2,1]);
+		assert!(a.is_subset_of(&MultiSet::new_from_array(&[1,2,2,3])));
+		assert!(!a.is_subset_of(&MultiSet::new_from_array(&[1,2,3])));
+	}
+
+	#[test] fn test_multiset_complement() {
+		let space = MultiSet::new_from_array(&[1,1,2,2,2]);
+		let subset = space.subset_from_indices(&[0,2,3]);
+		assert_eq!(*subset.complement().unwrap().set, &[1,2]);
+		assert_eq!(subset.parent_size(), Some(5));
+	}
+// endregion:
+
+// region: test_array_set
+	#[test] fn test_array_set_borrows() {
+		let array = [1,2,3,4,5];
+		let set = ArraySet::new_from_array(&array);
+		assert!(matches!(set.set, Cow::Borrowed(_)));
+		assert_eq!(set.size(), 5);
+		assert!(!set.is_subset());
+
+		let subset = set.subset_from_range(1, 4);
+		assert!(matches!(subset.set, Cow::Borrowed(_)));
+		assert_eq!(&subset[..], &[2,3,4]);
+		assert_eq!(subset.parent_size(), Some(5));
+	}
+
+	#[test] fn test_array_set_unique() {
+		let set = ArraySet::new_from_array(&[3,1,3,2,1]);
+		assert_eq!(&set[..], &[3,1,2]);
+		assert_eq!(ArraySet::<i32>::new().size(), 0);
+	}
+
+	#[test] fn test_array_set_unique_large() {
+		let values: Vec<u32> = (0..200_000).map(|i| (i * 7919) % 200_000).collect();
+		let set = ArraySet::new_from_array(&values);
+		assert!(matches!(set.set, Cow::Borrowed(_)));
+
+		let repeated: Vec<u32> = values.iter().chain(&values).copied().collect();
+		let set = ArraySet::new_from_array(&repeated);
+		assert!(matches!(set.set, Cow::Owned(_)));
+		assert_eq!(&set[..], &values[..]);
+	}
+
+	#[test] fn test_array_set_algebra() {
+		let space = ArraySet::new_from_array(&[1,2,3,4,5,6]);
+		let a = space.subset_from_indices(&[0,1,2,3]);
+		let b = space.subset_from_range(2, 5);
+
+		assert_eq!(&ArraySet::union(&a, &b)[..], &[1,2,3,4,5]);
+		assert_eq!(&ArraySet::intersection(&a, &b)[..], &[3,4]);
+		assert_eq!(&ArraySet::difference(&a, &b)[..], &[1,2]);
+		assert_eq!(&ArraySet::symmetric_difference(&a, &b)[..], &[1,2,5]);
+		assert_eq!(&a.complement().unwrap()[..], &[5,6]);
+		assert!(space.complement().is_none());
+
+		assert!(a.contains(&4));
+		assert!(!a.contains(&5));
+		assert!(a.is_disjoint(&b.subset_from_range(2, 3)));
+		assert!(!a.is_disjoint(&b));
+		assert!(space.is_superset_of(&a));
+		assert!(ArraySet::intersection(&a, &b).is_subset_of(&b));
+	}
+// endregion:
+
+// region: test_hashed_set
+	fn strings(words: &[&str]) -> Vec<String> {
+		words.iter().map(|w| w.to_string()).collect()
+	}
+
+	#[test] fn test_hashed_set() {
+		let space = HashedSet::new_from_array(&strings(&["a","b","c","d","b"]));
+		assert_eq!(space.size(), 4);
+		assert!(space.contains(&"c".to_string()));
+
+		let a = HashedSet::new_from_array(&strings(&["a","b"]));
+		let b = HashedSet::new_from_array(&strings(&["b","c"]));
+		let mut union: Vec<String> = HashedSet::union(&a, &b).iter().cloned().collect();
+		union.sort();
+		assert_eq!(union, strings(&["a","b","c"]));
+		assert_eq!(HashedSet::intersection(&a, &b).iter().collect::<Vec<_>>(), vec!["b"]);
+		assert_eq!(HashedSet::difference(&a, &b).iter().collect::<Vec<_>>(), vec!["a"]);
+		assert_eq!(HashedSet::symmetric_difference(&a, &b).size(), 2);
+		assert!(a.is_subset_of(&space) && space.is_superset_of(&b));
+		assert!(!a.is_disjoint(&b));
+	}
+
+	#[test] fn test_hashed_set_subsets() {
+		let space = HashedSet::from(HashSet::from([1,2,3,4,5]));
+		let subset = space.subset_from_indices(&[0,3]);
+		assert_eq!(subset.size(), 2);
+		assert_eq!(subset.parent_size(), Some(5));
+		assert!(subset.is_subset_of(&space));
+
+		let complement = subset.complement().unwrap();
+		assert_eq!(complement.size(), 3);
+		assert!(complement.is_disjoint(&subset));
+		assert_eq!(space.subset_from_range(1, 4).size(), 3);
+	}
+// endregion:
+
+// region: test_tree_set
+	#[test] fn test_tree_set() {
+		let space = TreeSet::new_from_array(&strings(&["pear","apple","fig","apple","kiwi"]));
+		assert_eq!(space.iter().cloned().collect::<Vec<_>>(), strings(&["apple","fig","kiwi","pear"]));
+
+		let subset = space.subset_from_range(1, 3);
+		assert_eq!(subset.iter().cloned().collect::<Vec<_>>(), strings(&["fig","kiwi"]));
+		assert_eq!(subset.parent_size(), Some(4));
+		assert_eq!(
+			subset.complement().unwrap().iter().cloned().collect::<Vec<_>>(),
+			strings(&["apple","pear"])
+		);
+
+		let other = space.subset_from_indices(&[3,2]);
+		assert_eq!(TreeSet::union(&subset, &other).iter().cloned().collect::<Vec<_>>(), strings(&["fig","kiwi","pear"]));
+		assert_eq!(TreeSet::intersection(&subset, &other).iter().cloned().collect::<Vec<_>>(), strings(&["kiwi"]));
+		assert_eq!(TreeSet::symmetric_difference(&subset, &other).iter().cloned().collect::<Vec<_>>(), strings(&["fig","pear"]));
+		assert!(!subset.is_subset_of(&other));
+		assert!(space.is_superset_of(&other));
+	}
+// endregion:
+
+// region: test_power_set
+	#[test] fn test_power_set() {
+		let set = VecSet::new_from_array(&[1,2,3]);
+		let subsets: Vec<Vec<i32>> = set.power_set().map(|s| s.set.to_vec()).collect();
+		assert_eq!(subsets, vec![
+			vec![], vec![1], vec![2], vec![1,2], vec![3], vec![1,3], vec![2,3], vec![1,2,3]
+		]);
+		assert!(set.power_set().all(|s| s.parent_size() == Some(3)));
+	}
+
+	#[test] fn test_power_set_empty() {
+		let set = VecSet::<i32>::new();
+		assert_eq!(set.power_set().count(), 1);
+		assert_eq!(TreeSet::new_from_array(&["a","b","c","d"]).power_set().count(), 16);
+	}
+// endregion:
+
+// region: test_cartesian_product
+	#[test] fn test_cartesian_product() {
+		let coin = VecSet::new_from_array(&['H','T']);
+		let flips: Vec<Vec<char>> = cartesian_product(&[&coin, &coin, &coin]).collect();
+		assert_eq!(flips.len(), 8);
+		assert_eq!(flips[0], ['H','H','H']);
+		assert_eq!(flips[1], ['H','H','T']);
+		assert_eq!(flips[7], ['T','T','T']);
+
+		// three flips as a sample space
+		let space = TreeSet::new_from_array(&flips);
+		assert_eq!(space.size(), 8);
+	}
+
+	#[test] fn test_cartesian_product_edges() {
+		let a = VecSet::new_from_array(&[1,2]);
+		let empty = VecSet::<i32>::new();
+		assert_eq!(cartesian_product(&[&a, &empty]).count(), 0);
+		assert_eq!(cartesian_product::<i32>(&[]).collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+	}
+
+	#[test] fn test_cartesian_pairs() {
+		let die = VecSet::new_from_array(&[1,2,3,4,5,6]);
+		let rolls: Vec<(i32, i32)> = cartesian_pairs(&die, &die).collect();
+		assert_eq!(rolls.len(), 36);
+
+		// two dice as a sample space, rolling a seven
+		let space = VecSet::new_from_array(&rolls);
+		let sevens: Vec<usize> = (0..space.size()).filter(|i| space[*i].0 + space[*i].1 == 7).collect();
+		assert_eq!(space.subset_from_indices(&sevens).size(), 6);
+		assert_eq!(space.subset_from_indices(&sevens).parent_size(), Some(36));
+	}
+// endregion:
+
+// region: test_filter
+	#[test] fn test_filter() {
+		let space = VecSet::new_from_array(&[1,2,3,4,5,6]);
+		let even = space.filter(|x| x % 2 == 0);
+		assert_eq!(*even.set, &[2,4,6]);
+		assert_eq!(even.parent_size(), Some(6));
+		assert_eq!(*even.complement().unwrap().set, &[1,3,5]);
+		assert_eq!(space.filter(|x| *x > 10).size(), 0);
+	}
+
+	#[test] fn test_filter_other_sets() {
+		let multi = MultiSet::new_from_array(&[1,1,2,3,3,3]);
+		assert_eq!(*multi.filter(|x| *x != 2).set, &[1,1,3,3,3]);
+
+		let array = [1,2,3,4];
+		let slice = ArraySet::new_from_array(&array);
+		assert_eq!(&slice.filter(|x| *x < 3)[..], &[1,2]);
+		assert_eq!(slice.filter(|x| *x < 3).parent_size(), Some(4));
+
+		let words = TreeSet::new_from_array(&["ant".to_string(), "bee".to_string(), "cat".to_string()]);
+		let short = words.filter(|w| w.contains('a'));
+		assert_eq!(short.iter().cloned().collect::<Vec<_>>(), ["ant", "cat"]);
+		assert_eq!(HashedSet::new_from_array(&[1,2,3]).filter(|x| *x > 1).parent_size(), Some(3));
+	}
+// endregion:
+
+// region: test_map_and_iter
+	#[test] fn test_map() {
+		let set = VecSet::new_from_array(&[-2,-1,0,1,2]);
+		let squares = set.map(|x| x * x);
+		assert_eq!(*squares.set, &[4,1,0]);
+		assert!(!squares.is_subset());
+	}
+
+	#[test] fn test_iter() {
+		let set: VecSet<i32> = (1..=4).chain(2..=5).collect();
+		assert_eq!(*set.set, &[1,2,3,4,5]);
+		assert_eq!(set.iter().sum::<i32>(), 15);
+
+		let mut doubled = Vec::new();
+		for x in &set {
+			doubled.push(x * 2);
+		}
+		assert_eq!(doubled, [2,4,6,8,10]);
+
+		let subset = set.subset_from_range(0, 2);
+		assert_eq!(subset.into_iter().collect::<Vec<_>>(), [1,2]);
+		assert_eq!(set.into_iter().count(), 5);
+	}
+// endregion:
+
+// region: test_weighted_set
+	#[test] fn test_weighted_set() {
+		let weights = [Rational::from(1), Rational::new_from_fraction(1, 2), Rational::from(2)];
+		let space = WeightedSet::new_weighted(&['a','b','c'], &weights).unwrap();
+		assert_eq!(space.mass(), Rational::new_from_fraction(7, 2));
+		assert_eq!(space.weight(&'b'), Some(Rational::new_from_fraction(1, 2)));
+		assert_eq!(space.weight(&'z'), None);
+
+		let a = space.subset_from_indices(&[0,1,0]);
+		let c = space.filter(|x| *x == 'c');
+		assert_eq!(a.size(), 2);
+		assert_eq!(a.parent_mass(), Some(Rational::new_from_fraction(7, 2)));
+		assert_eq!(WeightedSet::union(&a, &c).mass(), space.mass());
+		assert_eq!(a.complement().unwrap().iter().map(|(x, _)| *x).collect::<Vec<_>>(), ['c']);
+		assert!(a.is_disjoint(&c));
+		assert!(space.is_superset_of(&c));
+		assert_eq!(WeightedSet::symmetric_difference(&a, &space).mass(), Rational::from(2));
+	}
+// endregion:
+
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use crate::math::datatypes::bigint::BigInt;
+use crate::math::primes::PrimeSieve;
+
+const SMALL_PRIME_LIMIT: u32 = 65536;
+
+// every u32 can be factored with the primes up to its square root
+fn small_primes() -> &'static PrimeSieve {
+	static SIEVE: OnceLock<PrimeSieve> = OnceLock::new();
+	SIEVE.get_or_init(|| PrimeSieve::new(SMALL_PRIME_LIMIT))
+}
+
+// MARK: PrimeFactorization
+
+/// A positive rational number stored as prime powers.
+///
+/// Each prime maps to its exponent, a negative exponent puts that prime in the
+/// denominator.  Multiplying and dividing only adds and subtracts exponents so
+/// values like n!/(r!(n-r)!) cancel completely without being multiplied out.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct PrimeFactorization {
+	powers: BTreeMap<u32, i32>,
+}
+
+impl PrimeFactorization {
+	/// Inits a new factorization equal to one
+	pub fn new() -> Self {
+		Self { powers: BTreeMap::new() }
+	}
+
+	/// Factors n into primes
+	/// panics if n is zero
+	pub fn of(n: u32) -> Self {
+		if n == 0 { panic!("zero has no prime factorization"); }
+
+		let mut ret_val = Self::new();
+		let mut rest = n;
+
+		for p in small_primes().primes() {
+			if (*p as u64) * (*p as u64) > rest as u64 { break; }
+			while rest.is_multiple_of(*p) {
+				ret_val.add_power(*p, 1);
+				rest /= p;
+			}
+		}
+
+		if rest > 1 { ret_val.add_power(rest, 1); }
+
+		ret_val
+	}
+
+	/// Factors the primes below 2^16 out of the absolute value of n, returning
+	/// them and the part of n left over, which is one or only has larger primes
+	/// panics if n is zero
+	pub fn split_small_primes(n: &BigInt) -> (Self, BigInt) {
+		if n.is_zero() { panic!("zero has no prime factorization"); }
+
+		let mut ret_val = Self::new();
+		let mut rest = n.abs();
+
+		// values that fit in a u128 are divided natively, they are nearly all of them
+		if let Some(mut small) = rest.to_u128() {
+			for p in small_primes().primes() {
+				if (*p as u128) * (*p as u128) > small { break; }
+				while small.is_multiple_of(*p as u128) {
+					ret_val.add_power(*p, 1);
+					small /= *p as u128;
+				}
+			}
+			// a prime is left over that may still be small enough to keep
+			if small > 1 && small <= SMALL_PRIME_LIMIT as u128 {
+				ret_val.add_power(small as u32, 1);
+				small = 1;
+			}
+			return (ret_val, BigInt::from(small))
+		}
+
+		for p in small_primes().primes() {
+			let divisor = BigInt::from(*p);
+			loop {
+				let (q, r) = rest.div_rem(&divisor);
+				if !r.is_zero() { break }
+				ret_val.add_power(*p, 1);
+				rest = q;
+			}
+		}
+
+		(ret_val, rest)
+	}
+
+	/// Factors n! using Legendre's formula, without multiplying anything out
+	pub fn factorial(n: u32) -> Self {
+		let mut ret_val = Self::new();
+
+		for p in PrimeSieve::new(n).primes() {
+			let mut exponent = 0;
+			let mut power = *p as u64;
+			while power <= n as u64 {
+				exponent += (n as u64 / power) as i32;
+				power *= *p as u64;
+			}
+			ret_val.add_power(*p, exponent);
+		}
+
+		ret_val
+	}
+
+	/// Returns the exponent of prime p, zero if it is not a factor
+	pub fn exponent(&self, p: u32) -> i32 {
+		*self.powers.get(&p).unwrap_or(&0)
+	}
+
+	/// Returns the (prime, exponent) pairs in ascending order of prime
+	pub fn powers(&self) -> impl Iterator<Item = (u32, i32)> + '_ {
+		self.powers.iter().map(|(p, e)| (*p, *e))
+	}
+
+	/// Returns true if no prime has a negative exponent
+	pub fn is_integer(&self) -> bool {
+		self.powers.values().all(|e| *e > 0)
+	}
+
+	/// Multiplies the factorization by anouther one
+	pub fn multiply(&mut self, other: &Self) {
+		for (p, e) in other.powers() {
+			self.add_power(p, e);
+		}
+	}
+
+	/// Divides anouther factorization into this one
+	pub fn divide(&mut self, other: &Self) {
+		for (p, e) in other.powers() {
+			self.add_power(p, -e);
+		}
+	}
+
+	/// Returns the primes of the numerator, each repeated by its exponent
+	pub fn numerator_primes(&self) -> Vec<u32> {
+		self.expand(|e| e)
+	}
+
+	/// Returns the primes of the denominator, each repeated by its exponent
+	pub fn denominator_primes(&self) -> Vec<u32> {
+		self.expand(|e| -e)
+	}
+
+	/// Multiplies out the numerator
+	pub fn numerator(&self) -> BigInt {
+		self.multiply_out(|e| e)
+	}
+
+	/// Multiplies out the denominator
+	pub fn denominator(&self) -> BigInt {
+		self.multiply_out(|e| -e)
+	}
+
+	fn add_power(&mut self, p: u32, e: i32) {
+		let exponent = self.powers.entry(p).or_insert(0);
+		*exponent += e;
+		if *exponent == 0 { self.powers.remove(&p); }
+	}
+
+	fn expand(&self, side: impl Fn(i32) -> i32) -> Vec<u32> {
+		let mut ret_val = Vec::new();
+		for (p, e) in self.powers() {
+			for _ in 0..side(e).max(0) {
+				ret_val.push(p);
+			}
+		}
+		ret_val
+	}
+
+	fn multiply_out(&self, side: impl Fn(i32) -> i32) -> BigInt {
+		self.powers()
+			.filter(|(_, e)| side(*e) > 0)
+			.fold(BigInt::one(), |acc, (p, e)| &acc * &BigInt::from(p).pow(side(e) as u32))
+	}
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+// region: test_of
+	fn test_of(n: u32, expected: &[(u32, i32)]) {
+		let factors: Vec<(u32, i32)> = PrimeFactorization::of(n).powers().collect();
+		assert_eq!(factors, expected)
+	}
+
+	#[test] fn test_of_0() { test_of(1, &[]); }
+	#[test] fn test_of_1() { test_of(360, &[(2,3), (3,2), (5,1)]); }
+	#[test] fn test_of_2() { test_of(4294967291, &[(4294967291,1)]); }
+	#[test] fn test_of_3() { test_of(4294967295, &[(3,1), (5,1), (17,1), (257,1), (65537,1)]); }
+	#[test] fn test_of_4() { test_of(2147483648, &[(2,31)]); }
+
+	#[test] #[should_panic] fn test_of_zero() {
+		PrimeFactorization::of(0);
+	}
+// endregion:
+
+// region: test_split_small_primes
+	fn test_split_small_primes(n: &str, expected: &[(u32, i32)], rest: &str) {
+		let (factors, left) = PrimeFactorization::split_small_primes(&n.parse().unwrap());
+		assert_eq!(factors.powers().collect::<Vec<_>>(), expected);
+		assert_eq!(left.to_string(), rest);
+	}
+
+	#[test] fn test_split_small_primes_0() { test_split_small_primes("-360", &[(2,3), (3,2), (5,1)], "1"); }
+	#[test] fn test_split_small_primes_1() { test_split_small_primes("4294967295", &[(3,1), (5,1), (17,1), (257,1)], "65537"); }
+	#[test] fn test_split_small_primes_2() { test_split_small_primes("131074", &[(2,1)], "65537"); }
+	#[test] fn test_split_small_primes_3() { test_split_small_primes("65521", &[(65521,1)], "1"); }
+	// 2^128 * 3 * (2^31 - 1) does not fit in a u128
+	#[test] fn test_split_small_primes_4() { test_split_small_primes(
+		"2192252454975507276542711858684300707188594180096",
+		&[(2,128), (3,1)],
+		"2147483647"
+	);}
+// endregion:
+
+// region: test_factorial
+	fn test_factorial(n: u32, expected: &[(u32, i32)]) {
+		let factors: Vec<(u32, i32)> = PrimeFactorization::factorial(n).powers().collect();
+		assert_eq!(factors, expected)
+	}
+
+	#[test] fn test_factorial_0() { test_factorial(0, &[]); }
+	#[test] fn test_factorial_1() { test_factorial(1, &[]); }
+	#[test] fn test_factorial_2() { test_factorial(10, &[(2,8), (3,4), (5,2), (7,1)]); }
+	#[test] fn test_factorial_3() {
+		assert_eq!(
+			PrimeFactorization::factorial(40).numerator().to_string(),
+			"815915283247897734345611269596115894272000000000"
+		);
+	}
+// endregion:
+
+	#[test] fn test_binomial_cancels_completely() {
+		let mut value = PrimeFactorization::factorial(10);
+		value.divide(&PrimeFactorization::factorial(4));
+		value.divide(&PrimeFactorization::factorial(6));
+
+		assert!(value.is_integer());
+		assert_eq!(value.numerator_primes(), &[2,3,5,7]);
+		assert_eq!(value.denominator(), BigInt::one());
+	}
+
+	#[test] fn test_multiply_divide() {
+		let mut value = PrimeFactorization::of(12);
+		value.divide(&PrimeFactorization::of(18));
+		assert_eq!(value.numerator_primes(), &[2]);
+		assert_eq!(value.denominator_primes(), &[3]);
+
+		value.multiply(&PrimeFactorization::of(3));
+		assert_eq!(value, PrimeFactorization::of(2));
+	}
+}
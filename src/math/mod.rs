@@ -5,6 +5,12 @@ pub mod datatypes;
 /// This modual contains functions related to statistics
 pub mod statistics;
 
+/// This modual contains a prime sieve and prime related functions
+pub mod primes;
+
+/// This modual contains the prime factorization of integers and rationals
+pub mod factorization;
+
 /// Prints the description of the math modual
 pub fn description() {
 	println!("This is the math modual");
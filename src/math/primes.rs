@@ -0,0 +1,106 @@
+// MARK: PrimeSieve
+
+/// A sieve of Eratosthenes holding every prime up to a limit.
+///
+/// Numbers above the limit are still answered by `is_prime`, using trial
+/// division by the sieved primes.
+pub struct PrimeSieve {
+	limit: u32,
+	composite: Vec<bool>,
+	primes: Vec<u32>,
+}
+
+impl PrimeSieve {
+	/// Sieves every number from 0 to limit inclusive
+	pub fn new(limit: u32) -> Self {
+		let mut composite = vec![false; limit as usize + 1];
+		let mut primes = Vec::new();
+
+		for i in 2..=limit as usize {
+			if composite[i] { continue; }
+			primes.push(i as u32);
+
+			let mut j = i * i;
+			while j <= limit as usize {
+				composite[j] = true;
+				j += i;
+			}
+		}
+
+		Self { limit, composite, primes }
+	}
+
+	/// returns the largest number that was sieved
+	pub fn limit(&self) -> u32 {
+		self.limit
+	}
+
+	/// returns every prime up to the limit in ascending order
+	pub fn primes(&self) -> &[u32] {
+		&self.primes
+	}
+
+	/// returns every sieved prime less than or equal to n
+	pub fn primes_up_to(&self, n: u32) -> &[u32] {
+		let end = self.primes.partition_point(|p| *p <= n);
+		&self.primes[..end]
+	}
+
+	/// returns true if n is prime
+	/// panics if n is above the limit and can not be checked with the sieved primes
+	pub fn is_prime(&self, n: u32) -> bool {
+		if n <= self.limit { return n >= 2 && !self.composite[n as usize] }
+
+		if (self.limit as u64 + 1) * (self.limit as u64 + 1) <= n as u64 {
+			panic!("{} is too large to check with a sieve up to {}", n, self.limit);
+		}
+
+		self.primes.iter()
+			.take_while(|p| (**p as u64) * (**p as u64) <= n as u64)
+			.all(|p| !n.is_multiple_of(*p))
+	}
+}
+
+/// returns every prime less than or equal to n
+pub fn primes_up_to(n: u32) -> Vec<u32> {
+	PrimeSieve::new(n).primes
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+// region: test_primes_up_to
+	fn test_primes_up_to(n: u32, expected: &[u32]) {
+		assert_eq!(primes_up_to(n), expected)
+	}
+
+	#[test] fn test_primes_up_to_0() { test_primes_up_to(0, &[0; 0]); }
+	#[test] fn test_primes_up_to_1() { test_primes_up_to(2, &[2]); }
+	#[test] fn test_primes_up_to_2() { test_primes_up_to(30, &[2,3,5,7,11,13,17,19,23,29]); }
+// endregion:
+
+// region: test_is_prime
+	fn test_is_prime(sieve: &PrimeSieve, n: u32, expected: bool) {
+		assert_eq!(sieve.is_prime(n), expected)
+	}
+
+	#[test] fn test_is_prime_0() { test_is_prime(&PrimeSieve::new(100), 1, false); }
+	#[test] fn test_is_prime_1() { test_is_prime(&PrimeSieve::new(100), 97, true); }
+	#[test] fn test_is_prime_2() { test_is_prime(&PrimeSieve::new(100), 91, false); }
+	#[test] fn test_is_prime_3() { test_is_prime(&PrimeSieve::new(100), 10007, true); }
+	#[test] fn test_is_prime_4() { test_is_prime(&PrimeSieve::new(100), 10199, false); }
+
+	#[test] #[should_panic] fn test_is_prime_too_large() {
+		PrimeSieve::new(10).is_prime(1000);
+	}
+// endregion:
+
+	#[test] fn test_primes_up_to_from_sieve() {
+		let sieve = PrimeSieve::new(50);
+		assert_eq!(sieve.primes_up_to(12), &[2,3,5,7,11]);
+		assert_eq!(sieve.primes_up_to(1), &[0; 0]);
+		assert_eq!(sieve.limit(), 50);
+	}
+}
@@ -1,7 +1,16 @@
 
+use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::math::datatypes::bigint::BigInt;
 use crate::math::datatypes::rational::Rational;
 use crate::math::datatypes::set::TSetElement;
 use crate::math::datatypes::set::VecSet;
+use crate::math::datatypes::set::MultiSet;
+use crate::math::datatypes::set::ArraySet;
+use crate::math::datatypes::set::HashedSet;
+use crate::math::datatypes::set::TreeSet;
 use crate::math::datatypes::set::TSet;
 
 
@@ -10,10 +19,21 @@ pub fn description() {
 	println!("This is the combinatorics modual");
 }
 
+// Every counting function returns None instead of a wrong number.  That is
+// when the selection is impossible (r > n) or the count does not fit in the
+// return type.  Selecting nothing is always possible in exactly one way, so
+// nC0 = nP0 = 1 for every n including zero.
+
 
 
-/// n C r combination of n objects taken r at a time
+/// n C r combination of n objects taken r at a time, nC0 is 1
+/// returns None if r > n or the result does not fit in a u128
 pub fn combinations(n: u32 , r: u32) -> Option<u128> {
+	combinations_big(n, r)?.to_u128()
+}
+
+/// n C r combination of n objects taken r at a time as an exact BigInt
+pub fn combinations_big(n: u32, r: u32) -> Option<BigInt> {
 	if r > n { return None; }
 	
 	let mut a = Rational::factorial(n);
@@ -23,12 +43,18 @@ pub fn combinations(n: u32 , r: u32) -> Option<u128> {
 	a.divide(&b);
 	a.divide(&c);
 
-	Some(a.evaluate() as u128)
+	Some(a.evaluate_big())
 }
 
 
-/// n P r permutations of n objects taken r at a time.
+/// n P r permutations of n objects taken r at a time, nP0 is 1
+/// returns None if r > n or the result does not fit in a u128
 pub fn permutations(n: u32, r: u32) -> Option<u128> {
+	permutations_big(n, r)?.to_u128()
+}
+
+/// n P r permutations of n objects taken r at a time as an exact BigInt
+pub fn permutations_big(n: u32, r: u32) -> Option<BigInt> {
 	if r > n { return None; }
 
 	let mut a = Rational::factorial(n);
@@ -36,9 +62,441 @@ pub fn permutations(n: u32, r: u32) -> Option<u128> {
 
 	a.divide(&b);
 
-	Some(a.evaluate() as u128)
+	Some(a.evaluate_big())
+}
+
+
+// MARK: Extended counting
+
+/// Multinomial coefficient (k1 + k2 + ..)! / (k1! k2! ..), the number of ways to
+/// split a set into groups of the given sizes
+/// returns None if the total does not fit in a u32 or the result does not fit in a u128
+pub fn multinomial(counts: &[u32]) -> Option<u128> {
+	let total = counts.iter().try_fold(0u32, |acc, k| acc.checked_add(*k))?;
+
+	let mut a = Rational::factorial(total);
+	for k in counts {
+		a.divide(&Rational::factorial(*k));
+	}
+
+	a.evaluate_big().to_u128()
+}
+
+/// n multichoose r, combinations of n objects taken r at a time with repetition
+/// returns None if r > 0 is chosen from nothing or the result does not fit in a u128
+pub fn combinations_with_repetition(n: u32, r: u32) -> Option<u128> {
+	if n == 0 { return if r == 0 { Some(1) } else { None } }
+
+	combinations(n.checked_add(r)? - 1, r)
+}
+
+/// Unsigned Stirling number of the first kind, permutations of n objects with
+/// exactly k cycles.  Zero when k > n
+/// returns None if the result does not fit in a u128
+pub fn stirling_first(n: u32, k: u32) -> Option<u128> {
+	if k > n { return Some(0) }
+
+	// row i holds c(i, 0..=k) and c(i, j) = c(i-1, j-1) + (i-1) c(i-1, j)
+	let mut row = vec![BigInt::zero(); k as usize + 1];
+	row[0] = BigInt::one();
+	for i in 1..=n {
+		for j in (0..=k as usize).rev() {
+			let stay = &row[j] * &BigInt::from(i - 1);
+			row[j] = if j == 0 { stay } else { &row[j-1] + &stay };
+		}
+	}
+
+	row[k as usize].to_u128()
+}
+
+/// Stirling number of the second kind, ways to partition n objects into exactly
+/// k non-empty groups.  Zero when k > n
+/// returns None if the result does not fit in a u128
+pub fn stirling_second(n: u32, k: u32) -> Option<u128> {
+	stirling_second_big(n, k).to_u128()
+}
+
+// S(n, k) = 1/k! * sum of (-1)^j kCj (k-j)^n
+fn stirling_second_big(n: u32, k: u32) -> BigInt {
+	if k > n { return BigInt::zero() }
+
+	let mut sum = BigInt::zero();
+	for j in 0..=k {
+		let term = &combinations_big(k, j).unwrap_or_default() * &BigInt::from(k - j).pow(n);
+		sum = if j % 2 == 0 { &sum + &term } else { &sum - &term };
+	}
+
+	&sum / &Rational::factorial(k).evaluate_big()
+}
+
+/// Bell number, the number of ways to partition a set of n objects
+/// returns None if the result does not fit in a u128
+pub fn bell(n: u32) -> Option<u128> {
+	// Bell triangle, each row starts with the last entry of the row above
+	let mut row = vec![BigInt::one()];
+	for _ in 0..n {
+		let mut next = vec![row[row.len() - 1].clone()];
+		for value in &row {
+			let sum = &next[next.len() - 1] + value;
+			next.push(sum);
+		}
+		row = next;
+	}
+
+	row[0].to_u128()
+}
+
+/// Catalan number (2n)! / ((n+1)! n!)
+/// returns None if 2n does not fit in a u32 or the result does not fit in a u128
+pub fn catalan(n: u32) -> Option<u128> {
+	let mut a = Rational::factorial(n.checked_mul(2)?);
+	a.divide(&Rational::factorial(n + 1));
+	a.divide(&Rational::factorial(n));
+
+	a.evaluate_big().to_u128()
+}
+
+/// Derangements of n objects, permutations that leave no object in place.
+/// Uses !n = sum of (-1)^k n!/k!
+/// returns None if the result does not fit in a u128
+pub fn derangements(n: u32) -> Option<u128> {
+	let mut sum = BigInt::zero();
+	for k in 0..=n {
+		let mut term = Rational::factorial(n);
+		term.divide(&Rational::factorial(k));
+		let term = term.evaluate_big();
+		sum = if k % 2 == 0 { &sum + &term } else { &sum - &term };
+	}
+
+	sum.to_u128()
+}
+
+/// Number of integer partitions of n, ways to write n as a sum of positive
+/// integers ignoring order.  Uses Euler's pentagonal number recurrence
+/// returns None if the result does not fit in a u128
+pub fn partitions(n: u32) -> Option<u128> {
+	let n = n as usize;
+	let mut p = vec![BigInt::one()];
+
+	for i in 1..=n {
+		let mut sum = BigInt::zero();
+		for k in 1.. {
+			// generalized pentagonal numbers k(3k-1)/2 and k(3k+1)/2
+			let first = k * (3 * k - 1) / 2;
+			if first > i { break; }
+			let second = k * (3 * k + 1) / 2;
+
+			let mut term = p[i - first].clone();
+			if second <= i { term = &term + &p[i - second]; }
+			sum = if k % 2 == 1 { &sum + &term } else { &sum - &term };
+		}
+		p.push(sum);
+	}
+
+	p[n].to_u128()
 }
 
+/// Distinct r-combinations of a multiset where the i-th distinct element has
+/// multiplicities[i] copies.  Copies of an element are indistinguishable.
+/// returns None if r exceeds the total or the result does not fit in a u128
+pub fn multiset_combinations(multiplicities: &[u32], r: u32) -> Option<u128> {
+	multiset_count(multiplicities, r, |_, _| BigInt::one())
+}
+
+/// Distinct r-permutations of a multiset where the i-th distinct element has
+/// multiplicities[i] copies.  Copies of an element are indistinguishable.
+/// returns None if r exceeds the total or the result does not fit in a u128
+pub fn multiset_permutations(multiplicities: &[u32], r: u32) -> Option<u128> {
+	let total = multiplicities.iter().try_fold(0u32, |acc, m| acc.checked_add(*m))?;
+	if r > total { return None }
+
+	// placing k copies into a selection of length j can be done in jCk ways,
+	// read from Pascal's triangle rather than building each one
+	let mut pascal = vec![vec![BigInt::one()]];
+	for j in 1..=r as usize {
+		let row = (0..=j).map(|k| {
+			if k == 0 || k == j { BigInt::one() } else { &pascal[j - 1][k - 1] + &pascal[j - 1][k] }
+		}).collect();
+		pascal.push(row);
+	}
+
+	multiset_count(multiplicities, r, |j, k| pascal[j as usize][k as usize].clone())
+}
+
+// counts[j] is the number of selections of length j from the elements added
+// so far, each new element adds k copies to a selection in ways(j, k) ways
+fn multiset_count(multiplicities: &[u32], r: u32, ways: impl Fn(u32, u32) -> BigInt) -> Option<u128> {
+	let total = multiplicities.iter().try_fold(0u32, |acc, m| acc.checked_add(*m))?;
+	if r > total { return None }
+
+	let r = r as usize;
+	let mut counts = vec![BigInt::zero(); r + 1];
+	counts[0] = BigInt::one();
+
+	for m in multiplicities {
+		let mut next = vec![BigInt::zero(); r + 1];
+		for (j, value) in next.iter_mut().enumerate() {
+			for k in 0..=(*m as usize).min(j) {
+				let term = &counts[j - k] * &ways(j as u32, k as u32);
+				*value = &*value + &term;
+			}
+		}
+		counts = next;
+	}
+
+	counts[r].to_u128()
+}
+
+// MARK: Ranking
+
+// nCr and nPr for usize arguments, None if they do not fit in a u32
+fn count_combinations(n: usize, r: usize) -> Option<u128> {
+	combinations(u32::try_from(n).ok()?, u32::try_from(r).ok()?)
+}
+
+fn count_permutations(n: usize, r: usize) -> Option<u128> {
+	permutations(u32::try_from(n).ok()?, u32::try_from(r).ok()?)
+}
+
+/// Lexicographic rank of an r-combination of the indices 0..n.
+///
+/// Uses the combinatorial number system on the complement of each index, so
+/// rank = nCr - 1 - sum of (n-1-c_i) C (r-i).
+/// returns None if the combination is not strictly increasing, has an index
+/// out of range, or the rank does not fit in a u128
+pub fn rank_combination(n: usize, combination: &[usize]) -> Option<u128> {
+	if combination.windows(2).any(|w| w[0] >= w[1]) { return None }
+	if combination.last().is_some_and(|c| *c >= n) { return None }
+
+	let r = combination.len();
+	let mut ret_val = count_combinations(n, r)? - 1;
+	for (i, c) in combination.iter().enumerate() {
+		ret_val -= count_combinations(n - 1 - c, r - i).unwrap_or(0);
+	}
+
+	Some(ret_val)
+}
+
+/// The r-combination of the indices 0..n with the given lexicographic rank
+/// returns None if rank is not less than nCr
+pub fn unrank_combination(n: usize, r: usize, rank: u128) -> Option<Vec<usize>> {
+	if rank >= count_combinations(n, r)? { return None }
+
+	let mut ret_val = Vec::with_capacity(r);
+	let mut rest = rank;
+	let mut c = 0;
+	for i in 0..r {
+		// skip every block of combinations that starts with a smaller index
+		loop {
+			let block = count_combinations(n - 1 - c, r - 1 - i)?;
+			if rest < block { break; }
+			rest -= block;
+			c += 1;
+		}
+		ret_val.push(c);
+		c += 1;
+	}
+
+	Some(ret_val)
+}
+
+/// Lehmer code of an r-permutation of the indices 0..n.
+///
+/// Each entry counts the unused indices smaller than the one chosen at that
+/// position, so entry i is always less than n - i.
+/// returns None if the permutation repeats an index or has one out of range
+pub fn lehmer_code(n: usize, permutation: &[usize]) -> Option<Vec<usize>> {
+	let mut used = vec![false; n];
+	let mut ret_val = Vec::with_capacity(permutation.len());
+
+	for p in permutation {
+		if *p >= n || used[*p] { return None }
+		ret_val.push(used[..*p].iter().filter(|u| !**u).count());
+		used[*p] = true;
+	}
+
+	Some(ret_val)
+}
+
+/// Builds the r-permutation of the indices 0..n from its Lehmer code
+/// returns None if an entry is too large for its position or the code is longer than n
+pub fn from_lehmer_code(n: usize, code: &[usize]) -> Option<Vec<usize>> {
+	if code.len() > n { return None }
+
+	let mut unused: Vec<usize> = (0..n).collect();
+	let mut ret_val = Vec::with_capacity(code.len());
+	for d in code {
+		if *d >= unused.len() { return None }
+		ret_val.push(unused.remove(*d));
+	}
+
+	Some(ret_val)
+}
+
+/// Lexicographic rank of an r-permutation of the indices 0..n,
+/// the sum of each Lehmer code entry times (n-1-i) P (r-1-i)
+/// returns None if the permutation is invalid or the rank does not fit in a u128
+pub fn rank_permutation(n: usize, permutation: &[usize]) -> Option<u128> {
+	let r = permutation.len();
+	let mut ret_val: u128 = 0;
+
+	for (i, d) in lehmer_code(n, permutation)?.iter().enumerate() {
+		let block = count_permutations(n - 1 - i, r - 1 - i)?;
+		ret_val = ret_val.checked_add(block.checked_mul(*d as u128)?)?;
+	}
+
+	Some(ret_val)
+}
+
+/// The r-permutation of the indices 0..n with the given lexicographic rank
+/// returns None if rank is not less than nPr
+pub fn unrank_permutation(n: usize, r: usize, rank: u128) -> Option<Vec<usize>> {
+	if rank >= count_permutations(n, r)? { return None }
+
+	let mut code = Vec::with_capacity(r);
+	let mut rest = rank;
+	for i in 0..r {
+		let block = count_permutations(n - 1 - i, r - 1 - i)?;
+		code.push((rest / block) as usize);
+		rest %= block;
+	}
+
+	from_lehmer_code(n, &code)
+}
+
+// MARK: Enumeration
+
+/// Lazy iterator over every r-combination of the indices 0..n.
+///
+/// Each combination is an increasing index vec and they are yielded in
+/// lexicographic order, only the current combination is held in memory.
+pub struct CombinationIndices {
+	n: usize,
+	indices: Vec<usize>,
+	done: bool,
+}
+
+impl CombinationIndices {
+	/// Starts at [0, 1, .., r-1], yields nothing if r > n
+	pub fn new(n: usize, r: usize) -> Self {
+		Self { n, indices: (0..r).collect(), done: r > n }
+	}
+}
+
+impl Iterator for CombinationIndices {
+	type Item = Vec<usize>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None }
+
+		let ret_val = self.indices.clone();
+		let r = self.indices.len();
+
+		// find the rightmost index that can still move right
+		match (0..r).rev().find(|i| self.indices[*i] != i + self.n - r) {
+			None => self.done = true,
+			Some(i) => {
+				self.indices[i] += 1;
+				for j in i+1..r {
+					self.indices[j] = self.indices[j-1] + 1;
+				}
+			}
+		}
+
+		Some(ret_val)
+	}
+}
+
+/// Lazy iterator over every r-permutation of the indices 0..n.
+///
+/// Permutations are yielded in lexicographic order, only the current
+/// arrangement and its cycle counters are held in memory.
+pub struct PermutationIndices {
+	r: usize,
+	indices: Vec<usize>,
+	cycles: Vec<usize>,
+	started: bool,
+	done: bool,
+}
+
+impl PermutationIndices {
+	/// Starts at [0, 1, .., r-1], yields nothing if r > n
+	pub fn new(n: usize, r: usize) -> Self {
+		Self {
+			r,
+			indices: (0..n).collect(),
+			cycles: (0..r.min(n)).map(|i| n - i).collect(),
+			started: false,
+			done: r > n,
+		}
+	}
+}
+
+impl Iterator for PermutationIndices {
+	type Item = Vec<usize>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done { return None }
+
+		if !self.started {
+			self.started = true;
+			return Some(self.indices[..self.r].to_vec())
+		}
+
+		let n = self.indices.len();
+		for i in (0..self.r).rev() {
+			self.cycles[i] -= 1;
+			if self.cycles[i] == 0 {
+				// every choice for position i is used, rotate it to the back
+				self.indices[i..].rotate_left(1);
+				self.cycles[i] = n - i;
+			}
+			else {
+				let j = n - self.cycles[i];
+				self.indices.swap(i, j);
+				return Some(self.indices[..self.r].to_vec())
+			}
+		}
+
+		self.done = true;
+		None
+	}
+}
+
+/// Lazy iterator over the r-combinations of a set, yielding subsets of it
+pub struct Combinations<'a, T, S> {
+	set: &'a S,
+	indices: CombinationIndices,
+	element: PhantomData<T>,
+}
+
+impl<'a, T, S: TSet<'a, T> + TCombinatorics<'a, T>> Iterator for Combinations<'a, T, S> {
+	type Item = S;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let set = self.set;
+		self.indices.find(|i| set.is_distinct_selection(i)).map(|i| set.subset_from_indices(&i))
+	}
+}
+
+/// Lazy iterator over the r-permutations of a set, yielding subsets of it
+/// that keep the order of the arrangement
+pub struct Permutations<'a, T, S> {
+	set: &'a S,
+	indices: PermutationIndices,
+	element: PhantomData<T>,
+}
+
+impl<'a, T, S: TSet<'a, T> + TCombinatorics<'a, T>> Iterator for Permutations<'a, T, S> {
+	type Item = S;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let set = self.set;
+		self.indices.find(|i| set.is_distinct_selection(i)).map(|i| set.subset_from_indices(&i))
+	}
+}
+
+// MARK: TCombinatorics
 
 /// TCombinatorics trait: structs with this trait have nCr and nPr avalible
 pub trait TCombinatorics<'a, T> {
@@ -47,18 +505,175 @@ pub trait TCombinatorics<'a, T> {
 
 	/// n P r permutations from a set taken r at a time
 	fn permutations(&self, r: u32) -> Option<u128>;
+
+	/// Returns false if the selection at these positions gives the same elements,
+	/// in the same order, as one that uses earlier copies of repeated elements.
+	/// Always true for sets whose elements are unique
+	fn is_distinct_selection(&self, _indices: &[usize]) -> bool {
+		true
+	}
+
+	/// Lazy iterator over every r-combination of the set as a subset,
+	/// in lexicographic order of the element positions
+	fn iter_combinations(&'a self, r: usize) -> Combinations<'a, T, Self>
+	where Self: TSet<'a, T> + Sized {
+		Combinations { set: self, indices: CombinationIndices::new(self.size(), r), element: PhantomData }
+	}
+
+	/// Lazy iterator over every r-permutation of the set as an ordered subset,
+	/// in lexicographic order of the element positions
+	fn iter_permutations(&'a self, r: usize) -> Permutations<'a, T, Self>
+	where Self: TSet<'a, T> + Sized {
+		Permutations { set: self, indices: PermutationIndices::new(self.size(), r), element: PhantomData }
+	}
+
+	/// The r-combination with lexicographic rank k as a subset, without enumerating
+	/// returns None if k is out of range
+	fn nth_combination(&'a self, r: usize, k: u128) -> Option<Self>
+	where Self: TSet<'a, T> + Sized {
+		Some(self.subset_from_indices(&unrank_combination(self.size(), r, k)?))
+	}
+
+	/// The r-permutation with lexicographic rank k as an ordered subset, without enumerating
+	/// returns None if k is out of range
+	fn nth_permutation(&'a self, r: usize, k: u128) -> Option<Self>
+	where Self: TSet<'a, T> + Sized {
+		Some(self.subset_from_indices(&unrank_permutation(self.size(), r, k)?))
+	}
+}
+
+impl<'a, T: TSetElement> TCombinatorics<'a, T> for VecSet<T> {
+	fn combinations(&self, r:u32) -> Option<u128> {
+		combinations(u32::try_from(self.size()).ok()?, r)
+	}
+
+	fn permutations(&self, r: u32) -> Option<u128> {
+		permutations(u32::try_from(self.size()).ok()?, r)
+	}
+}
+
+impl<'a, T: TSetElement> TCombinatorics<'a, T> for ArraySet<'a, T> {
+	fn combinations(&self, r:u32) -> Option<u128> {
+		combinations(u32::try_from(self.size()).ok()?, r)
+	}
+
+	fn permutations(&self, r: u32) -> Option<u128> {
+		permutations(u32::try_from(self.size()).ok()?, r)
+	}
+}
+
+impl<'a, T: Clone + Eq + Hash> TCombinatorics<'a, T> for HashedSet<T> {
+	fn combinations(&self, r:u32) -> Option<u128> {
+		combinations(u32::try_from(self.size()).ok()?, r)
+	}
+
+	fn permutations(&self, r: u32) -> Option<u128> {
+		permutations(u32::try_from(self.size()).ok()?, r)
+	}
 }
 
-impl<'a, T: TSetElement> TCombinatorics<'a, T> for VecSet<'a, T> {
+impl<'a, T: Clone + Ord> TCombinatorics<'a, T> for TreeSet<T> {
 	fn combinations(&self, r:u32) -> Option<u128> {
-		combinations(self.size() as u32, r)
+		combinations(u32::try_from(self.size()).ok()?, r)
+	}
+
+	fn permutations(&self, r: u32) -> Option<u128> {
+		permutations(u32::try_from(self.size()).ok()?, r)
+	}
+}
+
+// Copies of an element are indistinguishable, so only distinct selections are
+// counted and enumerated.  A selection is kept when every copy it uses comes
+// after the earlier copies of that element in the selection.  The nth ones
+// are unranked from the multiplicities, in the same order as the iterators.
+impl<'a, T: TSetElement> TCombinatorics<'a, T> for MultiSet<T> {
+	fn combinations(&self, r: u32) -> Option<u128> {
+		multiset_combinations(&multiplicities(self)?, r)
 	}
 
 	fn permutations(&self, r: u32) -> Option<u128> {
-		permutations(self.size() as u32, r)
+		multiset_permutations(&multiplicities(self)?, r)
+	}
+
+	fn is_distinct_selection(&self, indices: &[usize]) -> bool {
+		indices.iter().enumerate().all(|(t, i)| {
+			(0..*i).all(|j| self[j] != self[*i] || indices[..t].contains(&j))
+		})
+	}
+
+	fn nth_combination(&'a self, r: usize, k: u128) -> Option<Self> {
+		Some(self.subset_from_indices(&unrank_multiset(self, r, k, false)?))
+	}
+
+	fn nth_permutation(&'a self, r: usize, k: u128) -> Option<Self> {
+		Some(self.subset_from_indices(&unrank_multiset(self, r, k, true)?))
 	}
 }
 
+fn multiplicities<T: TSetElement>(set: &MultiSet<T>) -> Option<Vec<u32>> {
+	set.counts().iter().map(|(_, m)| u32::try_from(*m).ok()).collect()
+}
+
+// Positions of the distinct selection with rank k, in the order the multiset
+// iterators yield them.  Each position takes the first candidate whose block
+// of completions, counted from the multiplicities left, still holds the rank
+fn unrank_multiset<T: TSetElement>(set: &MultiSet<T>, r: usize, k: u128, ordered: bool) -> Option<Vec<usize>> {
+	let n = set.size();
+	if r > n { return None }
+
+	// None when the count does not fit in a u128, so it holds every rank
+	let count = |left: &[u32], len: usize| {
+		if len as u64 > left.iter().map(|m| *m as u64).sum::<u64>() { return Some(0) }
+		if ordered { multiset_permutations(left, len as u32) } else { multiset_combinations(left, len as u32) }
+	};
+
+	let mut used = vec![false; n];
+	if count(&multiplicities_left(set, &used, None, ordered), r).is_some_and(|total| k >= total) { return None }
+
+	let mut ret_val: Vec<usize> = Vec::with_capacity(r);
+	let mut rest = k;
+	for i in 0..r {
+		let start = if ordered { 0 } else { ret_val.last().map_or(0, |p| p + 1) };
+		let mut chosen = None;
+
+		for q in start..n {
+			// only the first unused copy of an element can come next
+			if used[q] || (0..q).any(|j| !used[j] && set[j] == set[q]) { continue }
+
+			used[q] = true;
+			match count(&multiplicities_left(set, &used, Some(q), ordered), r - 1 - i) {
+				Some(block) if rest >= block => rest -= block,
+				_ => { chosen = Some(q); break }
+			}
+			used[q] = false;
+		}
+
+		ret_val.push(chosen?);
+	}
+
+	Some(ret_val)
+}
+
+// Copies of each element that can still be selected.  Combinations only move
+// forward from the last position, so an element with an unused copy before it
+// can not be selected again
+fn multiplicities_left<T: TSetElement>(set: &MultiSet<T>, used: &[bool], last: Option<usize>, ordered: bool) -> Vec<u32> {
+	let mut left: BTreeMap<T, Option<u32>> = BTreeMap::new();
+
+	for (j, is_used) in used.iter().enumerate() {
+		if *is_used { continue }
+		let copies = left.entry(set[j]).or_insert(Some(0));
+		if ordered || last.is_none_or(|p| j > p) {
+			*copies = copies.map(|c| c + 1);
+		}
+		else {
+			*copies = None;
+		}
+	}
+
+	left.values().map(|m| m.unwrap_or(0)).collect()
+}
+
 
 // MARK: Tests
 #[cfg(test)]
@@ -78,12 +693,12 @@ mod tests {
 	#[test] fn test_combinations_0() { test_combinations(
 		0,
 		0,
-		Some(0)
+		Some(1)
 	);}
 	#[test] fn test_combinations_1() { test_combinations(
 		10,
 		0,
-		Some(0)
+		Some(1)
 	);}
 	#[test] fn test_combinations_2() { test_combinations(
 		0,
@@ -110,6 +725,26 @@ mod tests {
 		8,
 		Some(45)
 	);}
+	#[test] fn test_combinations_7() { test_combinations(
+		40,
+		20,
+		Some(137846528820)
+	);}
+	#[test] fn test_combinations_8() { test_combinations(
+		200,
+		100,
+		None
+	);}
+	#[test] fn test_combinations_9() { test_combinations(
+		10,
+		10,
+		Some(1)
+	);}
+	#[test] fn test_combinations_10() { test_combinations(
+		1,
+		1,
+		Some(1)
+	);}
 // endregion:
 
 // region: test_permutations
@@ -120,12 +755,12 @@ mod tests {
 	#[test] fn test_permutations_0() { test_permutations(
 		0,
 		0,
-		Some(0)
+		Some(1)
 	);}
 	#[test] fn test_permutations_1() { test_permutations(
 		10,
 		0,
-		Some(0)
+		Some(1)
 	);}
 	#[test] fn test_permutations_2() { test_permutations(
 		0,
@@ -152,8 +787,475 @@ mod tests {
 		8,
 		Some(1814400)
 	);}
+	#[test] fn test_permutations_7() { test_permutations(
+		40,
+		30,
+		None
+	);}
+	#[test] fn test_permutations_8() { test_permutations(
+		10,
+		10,
+		Some(3628800)
+	);}
 	// endregion:
 
+// region: test_big
+	fn test_combinations_big(n: u32, r: u32, expected: Option<&str>) {
+		assert_eq!(combinations_big(n, r).map(|v| v.to_string()).as_deref(), expected);
+	}
+
+	#[test] fn test_combinations_big_0() { test_combinations_big(
+		200,
+		100,
+		Some("90548514656103281165404177077484163874504589675413336841320")
+	);}
+	#[test] fn test_combinations_big_1() { test_combinations_big(
+		10,
+		11,
+		None
+	);}
+	#[test] fn test_combinations_big_2() { test_combinations_big(
+		0,
+		0,
+		Some("1")
+	);}
+
+	fn test_permutations_big(n: u32, r: u32, expected: Option<&str>) {
+		assert_eq!(permutations_big(n, r).map(|v| v.to_string()).as_deref(), expected);
+	}
+
+	#[test] fn test_permutations_big_0() { test_permutations_big(
+		40,
+		30,
+		Some("224844379201911853600532206127677440000000")
+	);}
+	#[test] fn test_permutations_big_1() { test_permutations_big(
+		10,
+		2,
+		Some("90")
+	);}
+// endregion:
+
+// region: test_combination_indices
+	fn test_combination_indices(n: usize, r: usize, expected: &[&[usize]]) {
+		let combinations: Vec<Vec<usize>> = CombinationIndices::new(n, r).collect();
+		assert_eq!(combinations, expected);
+	}
+
+	#[test] fn test_combination_indices_0() { test_combination_indices(
+		4,
+		2,
+		&[&[0,1], &[0,2], &[0,3], &[1,2], &[1,3], &[2,3]]
+	);}
+	#[test] fn test_combination_indices_1() { test_combination_indices(
+		3,
+		0,
+		&[&[]]
+	);}
+	#[test] fn test_combination_indices_2() { test_combination_indices(
+		3,
+		3,
+		&[&[0,1,2]]
+	);}
+	#[test] fn test_combination_indices_3() { test_combination_indices(
+		2,
+		3,
+		&[]
+	);}
+	#[test] fn test_combination_indices_4() { test_combination_indices(
+		0,
+		0,
+		&[&[]]
+	);}
+// endregion:
+
+// region: test_permutation_indices
+	fn test_permutation_indices(n: usize, r: usize, expected: &[&[usize]]) {
+		let permutations: Vec<Vec<usize>> = PermutationIndices::new(n, r).collect();
+		assert_eq!(permutations, expected);
+	}
+
+	#[test] fn test_permutation_indices_0() { test_permutation_indices(
+		3,
+		3,
+		&[&[0,1,2], &[0,2,1], &[1,0,2], &[1,2,0], &[2,0,1], &[2,1,0]]
+	);}
+	#[test] fn test_permutation_indices_1() { test_permutation_indices(
+		3,
+		2,
+		&[&[0,1], &[0,2], &[1,0], &[1,2], &[2,0], &[2,1]]
+	);}
+	#[test] fn test_permutation_indices_2() { test_permutation_indices(
+		3,
+		0,
+		&[&[]]
+	);}
+	#[test] fn test_permutation_indices_3() { test_permutation_indices(
+		2,
+		3,
+		&[]
+	);}
+// endregion:
+
+	#[test] fn test_enumeration_matches_counts() {
+		for n in 0..7 {
+			for r in 0..=n {
+				assert_eq!(CombinationIndices::new(n, r).count() as u128, combinations(n as u32, r as u32).unwrap());
+				assert_eq!(PermutationIndices::new(n, r).count() as u128, permutations(n as u32, r as u32).unwrap());
+			}
+		}
+	}
+
+	#[test] fn test_enumeration_is_lazy() {
+		let mut combinations = CombinationIndices::new(20, 10);
+		assert_eq!(combinations.next(), Some((0..10).collect()));
+		assert_eq!(combinations.count(), 184755);
+	}
+
+// region: test_extended_counting
+	fn test_multinomial(counts: &[u32], expected: Option<u128>) {
+		assert_eq!(multinomial(counts), expected);
+	}
+
+	#[test] fn test_multinomial_0() { test_multinomial(&[2,3,4], Some(1260)); }
+	#[test] fn test_multinomial_1() { test_multinomial(&[], Some(1)); }
+	#[test] fn test_multinomial_2() { test_multinomial(&[0,0,5], Some(1)); }
+	#[test] fn test_multinomial_3() { test_multinomial(&[4,6], combinations(10, 4)); }
+	#[test] fn test_multinomial_4() { test_multinomial(&[u32::MAX, 1], None); }
+	#[test] fn test_multinomial_5() { test_multinomial(&[50,50,50], None); }
+
+	fn test_combinations_with_repetition(n: u32, r: u32, expected: Option<u128>) {
+		assert_eq!(combinations_with_repetition(n, r), expected);
+	}
+
+	#[test] fn test_combinations_with_repetition_0() { test_combinations_with_repetition(3, 2, Some(6)); }
+	#[test] fn test_combinations_with_repetition_1() { test_combinations_with_repetition(5, 0, Some(1)); }
+	#[test] fn test_combinations_with_repetition_2() { test_combinations_with_repetition(0, 0, Some(1)); }
+	#[test] fn test_combinations_with_repetition_3() { test_combinations_with_repetition(0, 2, None); }
+	#[test] fn test_combinations_with_repetition_4() { test_combinations_with_repetition(2, 10, Some(11)); }
+
+	fn test_stirling_first(n: u32, k: u32, expected: Option<u128>) {
+		assert_eq!(stirling_first(n, k), expected);
+	}
+
+	#[test] fn test_stirling_first_0() { test_stirling_first(0, 0, Some(1)); }
+	#[test] fn test_stirling_first_1() { test_stirling_first(4, 2, Some(11)); }
+	#[test] fn test_stirling_first_2() { test_stirling_first(5, 0, Some(0)); }
+	#[test] fn test_stirling_first_3() { test_stirling_first(3, 5, Some(0)); }
+	#[test] fn test_stirling_first_4() { test_stirling_first(10, 3, Some(1172700)); }
+	#[test] fn test_stirling_first_5() { test_stirling_first(30, 5, Some(49361465831621147825759587123200)); }
+	#[test] fn test_stirling_first_6() { test_stirling_first(6, 6, Some(1)); }
+
+	fn test_stirling_second(n: u32, k: u32, expected: Option<u128>) {
+		assert_eq!(stirling_second(n, k), expected);
+	}
+
+	#[test] fn test_stirling_second_0() { test_stirling_second(0, 0, Some(1)); }
+	#[test] fn test_stirling_second_1() { test_stirling_second(4, 2, Some(7)); }
+	#[test] fn test_stirling_second_2() { test_stirling_second(3, 0, Some(0)); }
+	#[test] fn test_stirling_second_3() { test_stirling_second(2, 5, Some(0)); }
+	#[test] fn test_stirling_second_4() { test_stirling_second(10, 3, Some(9330)); }
+	#[test] fn test_stirling_second_5() { test_stirling_second(40, 20, Some(162188909527975750487887236507181)); }
+
+	fn test_bell(n: u32, expected: Option<u128>) {
+		assert_eq!(bell(n), expected);
+	}
+
+	#[test] fn test_bell_0() { test_bell(0, Some(1)); }
+	#[test] fn test_bell_1() { test_bell(5, Some(52)); }
+	#[test] fn test_bell_2() { test_bell(10, Some(115975)); }
+	#[test] fn test_bell_3() { test_bell(30, Some(846749014511809332450147)); }
+	#[test] fn test_bell_4() { test_bell(100, None); }
+
+	fn test_catalan(n: u32, expected: Option<u128>) {
+		assert_eq!(catalan(n), expected);
+	}
+
+	#[test] fn test_catalan_0() { test_catalan(0, Some(1)); }
+	#[test] fn test_catalan_1() { test_catalan(5, Some(42)); }
+	#[test] fn test_catalan_2() { test_catalan(10, Some(16796)); }
+	#[test] fn test_catalan_3() { test_catalan(100, None); }
+
+	fn test_derangements(n: u32, expected: Option<u128>) {
+		assert_eq!(derangements(n), expected);
+	}
+
+	#[test] fn test_derangements_0() { test_derangements(0, Some(1)); }
+	#[test] fn test_derangements_1() { test_derangements(1, Some(0)); }
+	#[test] fn test_derangements_2() { test_derangements(4, Some(9)); }
+	#[test] fn test_derangements_3() { test_derangements(10, Some(1334961)); }
+	#[test] fn test_derangements_4() { test_derangements(34, Some(108610077126170304674801654684367969729)); }
+	#[test] fn test_derangements_5() { test_derangements(35, None); }
+
+	fn test_partitions(n: u32, expected: Option<u128>) {
+		assert_eq!(partitions(n), expected);
+	}
+
+	#[test] fn test_partitions_0() { test_partitions(0, Some(1)); }
+	#[test] fn test_partitions_1() { test_partitions(1, Some(1)); }
+	#[test] fn test_partitions_2() { test_partitions(5, Some(7)); }
+	#[test] fn test_partitions_3() { test_partitions(100, Some(190569292)); }
+// endregion:
+
+// region: test_multiset_counting
+	fn test_multiset_combinations(multiplicities: &[u32], r: u32, expected: Option<u128>) {
+		assert_eq!(multiset_combinations(multiplicities, r), expected);
+	}
+
+	#[test] fn test_multiset_combinations_0() { test_multiset_combinations(&[2,1], 2, Some(2)); }
+	#[test] fn test_multiset_combinations_1() { test_multiset_combinations(&[1,1,1,1,1], 2, Some(10)); }
+	#[test] fn test_multiset_combinations_2() { test_multiset_combinations(&[3,2,1], 3, Some(6)); }
+	#[test] fn test_multiset_combinations_3() { test_multiset_combinations(&[2,1], 0, Some(1)); }
+	#[test] fn test_multiset_combinations_4() { test_multiset_combinations(&[2,1], 4, None); }
+
+	fn test_multiset_permutations(multiplicities: &[u32], r: u32, expected: Option<u128>) {
+		assert_eq!(multiset_permutations(multiplicities, r), expected);
+	}
+
+	#[test] fn test_multiset_permutations_0() { test_multiset_permutations(&[2,1], 2, Some(3)); }
+	#[test] fn test_multiset_permutations_1() { test_multiset_permutations(&[1,1,1,1,1], 2, Some(20)); }
+	// MISSISSIPPI
+	#[test] fn test_multiset_permutations_2() { test_multiset_permutations(&[1,4,4,2], 11, Some(34650)); }
+	#[test] fn test_multiset_permutations_3() { test_multiset_permutations(&[2,1], 4, None); }
+// endregion:
+
+// region: test_rank_combination
+	fn test_rank_combination(n: usize, combination: &[usize], expected: Option<u128>) {
+		assert_eq!(rank_combination(n, combination), expected);
+		if let Some(rank) = expected {
+			assert_eq!(unrank_combination(n, combination.len(), rank).as_deref(), Some(combination));
+		}
+	}
+
+	#[test] fn test_rank_combination_0() { test_rank_combination(4, &[0,1], Some(0)); }
+	#[test] fn test_rank_combination_1() { test_rank_combination(4, &[1,2], Some(3)); }
+	#[test] fn test_rank_combination_2() { test_rank_combination(4, &[2,3], Some(5)); }
+	#[test] fn test_rank_combination_3() { test_rank_combination(5, &[], Some(0)); }
+	#[test] fn test_rank_combination_4() { test_rank_combination(4, &[2,1], None); }
+	#[test] fn test_rank_combination_5() { test_rank_combination(4, &[1,1], None); }
+	#[test] fn test_rank_combination_6() { test_rank_combination(4, &[1,4], None); }
+	#[test] fn test_rank_combination_7() { test_rank_combination(
+		100,
+		&[90,91,92,93,94,95,96,97,98,99],
+		Some(17310309456439)
+	);}
+
+	fn test_unrank_combination(n: usize, r: usize, rank: u128, expected: Option<&[usize]>) {
+		assert_eq!(unrank_combination(n, r, rank).as_deref(), expected);
+	}
+
+	#[test] fn test_unrank_combination_0() { test_unrank_combination(4, 2, 6, None); }
+	#[test] fn test_unrank_combination_1() { test_unrank_combination(3, 4, 0, None); }
+	#[test] fn test_unrank_combination_2() { test_unrank_combination(0, 0, 0, Some(&[])); }
+
+	#[test] fn test_combination_ranks_follow_enumeration() {
+		for (rank, combination) in CombinationIndices::new(7, 3).enumerate() {
+			assert_eq!(rank_combination(7, &combination), Some(rank as u128));
+			assert_eq!(unrank_combination(7, 3, rank as u128), Some(combination));
+		}
+	}
+// endregion:
+
+// region: test_lehmer_code
+	fn test_lehmer_code(n: usize, permutation: &[usize], expected: Option<&[usize]>) {
+		assert_eq!(lehmer_code(n, permutation).as_deref(), expected);
+		if let Some(code) = expected {
+			assert_eq!(from_lehmer_code(n, code).as_deref(), Some(permutation));
+		}
+	}
+
+	#[test] fn test_lehmer_code_0() { test_lehmer_code(4, &[0,1,2,3], Some(&[0,0,0,0])); }
+	#[test] fn test_lehmer_code_1() { test_lehmer_code(4, &[3,2,1,0], Some(&[3,2,1,0])); }
+	#[test] fn test_lehmer_code_2() { test_lehmer_code(5, &[2,0,4], Some(&[2,0,2])); }
+	#[test] fn test_lehmer_code_3() { test_lehmer_code(4, &[1,1], None); }
+	#[test] fn test_lehmer_code_4() { test_lehmer_code(4, &[4], None); }
+
+	#[test] fn test_from_lehmer_code_invalid() {
+		assert_eq!(from_lehmer_code(3, &[3]), None);
+		assert_eq!(from_lehmer_code(3, &[0,2]), None);
+		assert_eq!(from_lehmer_code(1, &[0,0]), None);
+	}
+// endregion:
+
+// region: test_rank_permutation
+	fn test_rank_permutation(n: usize, permutation: &[usize], expected: Option<u128>) {
+		assert_eq!(rank_permutation(n, permutation), expected);
+		if let Some(rank) = expected {
+			assert_eq!(unrank_permutation(n, permutation.len(), rank).as_deref(), Some(permutation));
+		}
+	}
+
+	#[test] fn test_rank_permutation_0() { test_rank_permutation(3, &[1,0], Some(2)); }
+	#[test] fn test_rank_permutation_1() { test_rank_permutation(3, &[2,1,0], Some(5)); }
+	#[test] fn test_rank_permutation_2() { test_rank_permutation(3, &[], Some(0)); }
+	#[test] fn test_rank_permutation_3() { test_rank_permutation(3, &[0,0], None); }
+	#[test] fn test_rank_permutation_4() { test_rank_permutation(
+		20,
+		&[19,18,17,16,15,14,13,12,11,10,9,8,7,6,5,4,3,2,1,0],
+		Some(2432902008176639999)
+	);}
+
+	#[test] fn test_unrank_permutation_out_of_range() {
+		assert_eq!(unrank_permutation(3, 2, 6), None);
+		assert_eq!(unrank_permutation(2, 3, 0), None);
+	}
+
+	#[test] fn test_permutation_ranks_follow_enumeration() {
+		for (rank, permutation) in PermutationIndices::new(5, 3).enumerate() {
+			assert_eq!(rank_permutation(5, &permutation), Some(rank as u128));
+			assert_eq!(unrank_permutation(5, 3, rank as u128), Some(permutation));
+		}
+	}
+// endregion:
+
+// region: test_nth
+	#[test] fn test_nth_combination() {
+		let set = VecSet::new_from_array(&[10,20,30,40,50]);
+		let subset = set.nth_combination(3, 4).unwrap();
+		assert_eq!(&subset[..], &[10,30,50]);
+		assert_eq!(subset.parent_size(), Some(5));
+		assert!(set.nth_combination(3, 10).is_none());
+	}
+
+	#[test] fn test_nth_permutation() {
+		let set = VecSet::new_from_array(&[10,20,30]);
+		assert_eq!(&set.nth_permutation(2, 3).unwrap()[..], &[20,30]);
+		assert_eq!(&set.nth_permutation(3, 5).unwrap()[..], &[30,20,10]);
+		assert!(set.nth_permutation(3, 6).is_none());
+	}
+// endregion:
+
+// region: test_iter_combinations
+	fn test_iter_combinations(set: VecSet<u32>, r: usize, expected: &[&[u32]]) {
+		let subsets: Vec<VecSet<u32>> = set.iter_combinations(r).collect();
+		assert_eq!(subsets.len(), expected.len());
+		for (subset, e) in subsets.iter().zip(expected) {
+			assert_eq!(&subset[..], *e);
+			assert_eq!(subset.parent_size(), Some(set.size()));
+		}
+	}
+
+	#[test] fn test_iter_combinations_0() { test_iter_combinations(
+		VecSet::new_from_array(&[1,2,3,4]),
+		3,
+		&[&[1,2,3], &[1,2,4], &[1,3,4], &[2,3,4]]
+	);}
+	#[test] fn test_iter_combinations_1() { test_iter_combinations(
+		VecSet::new_from_array(&[1,2,3,4]),
+		5,
+		&[]
+	);}
+// endregion:
+
+// region: test_iter_permutations
+	fn test_iter_permutations(set: VecSet<u32>, r: usize, expected: &[&[u32]]) {
+		let arrangements: Vec<VecSet<u32>> = set.iter_permutations(r).collect();
+		assert_eq!(arrangements.len(), expected.len());
+		for (arrangement, e) in arrangements.iter().zip(expected) {
+			assert_eq!(&arrangement[..], *e);
+		}
+	}
+
+	#[test] fn test_iter_permutations_0() { test_iter_permutations(
+		VecSet::new_from_array(&[5,6,7]),
+		2,
+		&[&[5,6], &[5,7], &[6,5], &[6,7], &[7,5], &[7,6]]
+	);}
+	#[test] fn test_iter_permutations_1() { test_iter_permutations(
+		VecSet::new_from_array(&[5,6,7]),
+		0,
+		&[&[]]
+	);}
+// endregion:
+
+// region: test_array_set_combinatorics
+	#[test] fn test_array_set_combinatorics() {
+		let array = [5,6,7,8];
+		let set = ArraySet::new_from_array(&array);
+		assert_eq!(set.combinations(2), Some(6));
+		assert_eq!(set.permutations(2), Some(12));
+
+		let subsets: Vec<Vec<u32>> = set.iter_combinations(3).map(|s| s[..].to_vec()).collect();
+		assert_eq!(subsets, vec![vec![5,6,7], vec![5,6,8], vec![5,7,8], vec![6,7,8]]);
+		assert_eq!(&set.nth_permutation(2, 11).unwrap()[..], &[8,7]);
+	}
+// endregion:
+
+// region: test_string_sets
+	#[test] fn test_tree_set_combinatorics() {
+		let words: Vec<String> = ["c","a","b"].iter().map(|w| w.to_string()).collect();
+		let set = TreeSet::new_from_array(&words);
+		assert_eq!(set.combinations(2), Some(3));
+
+		let pairs: Vec<Vec<String>> = set.iter_permutations(2)
+			.map(|s| s.iter().cloned().collect())
+			.collect();
+		assert_eq!(pairs.len(), 6);
+		assert_eq!(pairs[0], ["a","b"]);
+		assert_eq!(pairs[5], ["b","c"]);
+	}
+
+	#[test] fn test_hashed_set_combinatorics() {
+		let words: Vec<String> = ["w","x","y","z"].iter().map(|w| w.to_string()).collect();
+		let set = HashedSet::new_from_array(&words);
+		assert_eq!(set.permutations(2), Some(12));
+		assert_eq!(set.iter_combinations(2).count(), 6);
+		assert!(set.iter_combinations(2).all(|s| s.size() == 2 && s.is_subset_of(&set)));
+		assert_eq!(set.nth_combination(4, 0).unwrap().size(), 4);
+	}
+// endregion:
+
+// region: test_multiset_enumeration
+	#[test] fn test_multiset_iter_combinations() {
+		let set = MultiSet::new_from_array(&[1,2,1,3]);
+		let subsets: Vec<Vec<u32>> = set.iter_combinations(2).map(|s| s[..].to_vec()).collect();
+		assert_eq!(subsets, vec![vec![1,2], vec![1,1], vec![1,3], vec![2,3]]);
+		assert_eq!(set.combinations(2), Some(subsets.len() as u128));
+	}
+
+	#[test] fn test_multiset_iter_permutations() {
+		let set = MultiSet::new_from_array(&[1,1,2]);
+		let arrangements: Vec<Vec<u32>> = set.iter_permutations(2).map(|s| s[..].to_vec()).collect();
+		assert_eq!(arrangements, vec![vec![1,1], vec![1,2], vec![2,1]]);
+		assert_eq!(set.permutations(2), Some(3));
+		assert_eq!(set.permutations(3), Some(3));
+	}
+
+	#[test] fn test_multiset_nth() {
+		let set = MultiSet::new_from_array(&[1,1,2]);
+		assert_eq!(&set.nth_permutation(2, 2).unwrap()[..], &[2,1]);
+		assert_eq!(&set.nth_combination(2, 1).unwrap()[..], &[1,2]);
+		assert!(set.nth_combination(2, 2).is_none());
+	}
+
+	#[test] fn test_multiset_nth_matches_iterators() {
+		let set = MultiSet::new_from_array(&[2,1,3,1,2,1]);
+		for r in 0..=6 {
+			let combinations: Vec<Vec<u32>> = set.iter_combinations(r).map(|s| s[..].to_vec()).collect();
+			for (k, expected) in combinations.iter().enumerate() {
+				assert_eq!(&set.nth_combination(r, k as u128).unwrap()[..], &expected[..]);
+			}
+			assert!(set.nth_combination(r, combinations.len() as u128).is_none());
+
+			let permutations: Vec<Vec<u32>> = set.iter_permutations(r).map(|s| s[..].to_vec()).collect();
+			for (k, expected) in permutations.iter().enumerate() {
+				assert_eq!(&set.nth_permutation(r, k as u128).unwrap()[..], &expected[..]);
+			}
+			assert!(set.nth_permutation(r, permutations.len() as u128).is_none());
+		}
+		assert!(set.nth_combination(7, 0).is_none());
+	}
+
+	#[test] fn test_multiset_nth_large() {
+		// 20 copies of 1 then 20 of 2, far too many arrangements to enumerate
+		let elements: Vec<u32> = (0..40).map(|i| 1 + i / 20).collect();
+		let set = MultiSet::new_from_array(&elements);
+		let last = set.permutations(40).unwrap() - 1;
+		assert_eq!(&set.nth_permutation(40, last).unwrap()[..], &[[2; 20], [1; 20]].concat()[..]);
+		assert_eq!(&set.nth_combination(20, 10).unwrap()[..], &[[1; 10], [2; 10]].concat()[..]);
+	}
+// endregion:
+
 // region: test_set_combinations
 	fn test_set_combinations(set: VecSet<u32>, r: u32, expected: Option<u128>) {
 		assert_eq!(set.combinations(r), expected)
@@ -162,7 +1264,7 @@ mod tests {
 	#[test] fn test_set_combinations_0() { test_set_combinations(
 		VecSet::new_from_array(&[1,2,3,4,5]),
 		0,
-		Some(0)
+		Some(1)
 	);	}
 
 	#[test] fn test_set_combinations_1() { test_set_combinations(
@@ -182,6 +1284,12 @@ mod tests {
 		6,
 		None
 	);	}
+
+	#[test] fn test_set_combinations_4() { test_set_combinations(
+		VecSet::new(),
+		0,
+		Some(1)
+	);	}
 // endregion:
 
 // region: test_set_permutations
@@ -192,7 +1300,7 @@ mod tests {
 	#[test] fn test_set_permutations_0() { test_set_permutations(
 		VecSet::new_from_array(&[1,2,3,4,5]),
 		0,
-		Some(0)
+		Some(1)
 	);	}
 
 	#[test] fn test_set_permutations_1() { test_set_permutations(
@@ -212,6 +1320,12 @@ mod tests {
 		6,
 		None
 	);	}
+
+	#[test] fn test_set_permutations_4() { test_set_permutations(
+		VecSet::new_from_array(&[1,2,3,4,5]),
+		5,
+		Some(120)
+	);	}
 // endregion:
 
 
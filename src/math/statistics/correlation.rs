@@ -0,0 +1,189 @@
+use std::fmt::Display;
+
+use crate::math::datatypes::rkgtab::RkgTabN;
+use crate::math::statistics::descriptive::mean;
+
+// The functions over slices return None when x and y differ in length, have
+// less than two values or, for correlations, one of them is constant.  The
+// matrices put NaN in those places instead.
+
+// MARK: Slice statistics
+
+/// the sample covariance of x and y, divides by n - 1
+pub fn covariance(x: &[f64], y: &[f64]) -> Option<f64> {
+	if x.len() != y.len() || x.len() < 2 { return None }
+
+	let (mean_x, mean_y) = (mean(x)?, mean(y)?);
+	let sum: f64 = x.iter().zip(y).map(|(a, b)| (a - mean_x) * (b - mean_y)).sum();
+	Some(sum / (x.len() - 1) as f64)
+}
+
+/// Pearson's correlation coefficient, how close x and y are to a straight line
+pub fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+	let spread = (covariance(x, x)? * covariance(y, y)?).sqrt();
+	if spread == 0.0 { return None }
+	Some((covariance(x, y)? / spread).clamp(-1.0, 1.0))
+}
+
+/// Spearman's rank correlation coefficient, how close x and y are to always
+/// increasing or decreasing together, tied values share the mean of their ranks
+pub fn spearman(x: &[f64], y: &[f64]) -> Option<f64> {
+	if x.len() != y.len() { return None }
+	pearson(&ranks(x), &ranks(y))
+}
+
+// the rank of each value from 1, ties get the mean of the ranks they span
+fn ranks(data: &[f64]) -> Vec<f64> {
+	let mut order: Vec<usize> = (0..data.len()).collect();
+	order.sort_by(|a, b| data[*a].total_cmp(&data[*b]));
+
+	let mut ranks = vec![0.0; data.len()];
+	let mut start = 0;
+	for run in order.chunk_by(|a, b| data[*a] == data[*b]) {
+		let rank = start as f64 + (run.len() + 1) as f64 / 2.0;
+		run.iter().for_each(|i| ranks[*i] = rank);
+		start += run.len();
+	}
+	ranks
+}
+
+// MARK: Matrices
+
+/// the covariance of every pair of collumns of a 2d table
+/// None if the table is not 2d
+pub fn covariance_matrix<T>(table: &RkgTabN<T>) -> Option<RkgTabN<f64>>
+where T: Clone + Copy + Display + Into<f64> {
+	matrix(table, covariance)
+}
+
+/// the Pearson correlation of every pair of collumns of a 2d table
+/// None if the table is not 2d
+pub fn pearson_matrix<T>(table: &RkgTabN<T>) -> Option<RkgTabN<f64>>
+where T: Clone + Copy + Display + Into<f64> {
+	matrix(table, pearson)
+}
+
+/// the Spearman correlation of every pair of collumns of a 2d table
+/// None if the table is not 2d
+pub fn spearman_matrix<T>(table: &RkgTabN<T>) -> Option<RkgTabN<f64>>
+where T: Clone + Copy + Display + Into<f64> {
+	let shape = table.shape();
+	if shape.len() != 2 { return None }
+
+	// rank each collumn once rather than for every pair
+	let ranks: Vec<Vec<f64>> = collumns(table).iter().map(|c| ranks(c)).collect();
+	let flat: Vec<f64> = (0..shape[0]).flat_map(|i| ranks.iter().map(move |c| c[i])).collect();
+	let mut ranked = RkgTabN::from_flat(&flat, &shape);
+	if let Some(labels) = table.labels_by_axis(1) { ranked.set_labels_from_flat(&labels, &[1]); }
+
+	matrix(&ranked, pearson)
+}
+
+// f of every pair of collumns, labeled on both axes with the collumn labels
+fn matrix<T>(table: &RkgTabN<T>, f: impl Fn(&[f64], &[f64]) -> Option<f64>) -> Option<RkgTabN<f64>>
+where T: Clone + Copy + Display + Into<f64> {
+	if table.shape().len() != 2 { return None }
+
+	let cols = collumns(table);
+	let size = cols.len();
+	let mut flat = vec![f64::NAN; size * size];
+	for i in 0..size {
+		for j in i..size {
+			let value = f(&cols[i], &cols[j]).unwrap_or(f64::NAN);
+			flat[i * size + j] = value;
+			flat[j * size + i] = value;
+		}
+	}
+
+	let mut result = RkgTabN::from_flat(&flat, &[size, size]);
+	if let Some(labels) = table.labels_by_axis(1) {
+		result.set_labels_from_flat(&[labels.clone(), labels].concat(), &[0, 1]);
+	}
+	Some(result)
+}
+
+fn collumns<T: Clone + Copy + Display + Into<f64>>(table: &RkgTabN<T>) -> Vec<Vec<f64>> {
+	let shape = table.shape();
+	(0..shape[1]).map(|j| (0..shape[0]).map(|i| table.get(&[i, j]).into()).collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(actual: f64, expected: f64) {
+		assert!((actual - expected).abs() <= 1e-12, "expected {} but got {}", expected, actual);
+	}
+
+	const X: [f64; 5] = [1.0, 2.0, 3.0, 4.0, 5.0];
+	const Y: [f64; 5] = [2.0, 4.0, 5.0, 4.0, 5.0];
+
+	// region: test_slice
+	#[test] fn test_covariance() {
+		assert_close(covariance(&X, &Y).unwrap(), 1.5);
+		assert_close(covariance(&X, &X).unwrap(), 2.5);
+		assert_eq!(covariance(&X, &Y[..4]), None);
+		assert_eq!(covariance(&[1.0], &[2.0]), None);
+	}
+
+	#[test] fn test_pearson() {
+		assert_close(pearson(&X, &Y).unwrap(), 0.6f64.sqrt());
+		let reversed: Vec<f64> = X.iter().map(|x| 10.0 - 2.0 * x).collect();
+		assert_close(pearson(&X, &reversed).unwrap(), -1.0);
+		assert_eq!(pearson(&X, &[3.0; 5]), None);
+	}
+
+	#[test] fn test_spearman() {
+		// monotonic but not a line
+		let cubes: Vec<f64> = X.iter().map(|x| x.powi(3)).collect();
+		assert_close(spearman(&X, &cubes).unwrap(), 1.0);
+		assert!(pearson(&X, &cubes).unwrap() < 1.0);
+
+		// ranks of Y are 1, 2.5, 4.5, 2.5, 4.5
+		assert_eq!(ranks(&Y), vec![1.0, 2.5, 4.5, 2.5, 4.5]);
+		assert_close(spearman(&X, &Y).unwrap(), 0.7378647873726218);
+	}
+	// endregion:
+
+	// region: test_matrix
+	fn labeled_table() -> RkgTabN<f64> {
+		let data: Vec<f64> = (0..5).flat_map(|i| [X[i], Y[i], X[i].powi(3)]).collect();
+		let mut table = RkgTabN::from_flat(&data, &[5, 3]);
+		let labels: Vec<String> = ["x", "y", "cube"].iter().map(|l| l.to_string()).collect();
+		table.set_labels_from_flat(&labels, &[1]);
+		table
+	}
+
+	#[test] fn test_covariance_matrix() {
+		let matrix = covariance_matrix(&labeled_table()).unwrap();
+		assert_eq!(matrix.shape(), vec![3, 3]);
+		assert_close(matrix.get(&[0, 0]), 2.5);
+		assert_close(matrix.get(&[0, 1]), 1.5);
+		assert_close(matrix.get(&[1, 0]), 1.5);
+		assert_eq!(matrix.get_label_by_axis(0, 2), "cube");
+		assert_eq!(matrix.get_label_by_axis(1, 1), "y");
+	}
+
+	#[test] fn test_correlation_matrices() {
+		let table = labeled_table();
+		let pearson = pearson_matrix(&table).unwrap();
+		assert_close(pearson.get(&[1, 1]), 1.0);
+		assert_close(pearson.get(&[0, 1]), 0.6f64.sqrt());
+
+		let spearman = spearman_matrix(&table).unwrap();
+		assert_close(spearman.get(&[0, 2]), 1.0);
+		assert_close(spearman.get(&[2, 1]), 0.7378647873726218);
+		assert_eq!(spearman.labels_by_axis(1), table.labels_by_axis(1));
+		assert!(spearman.table2d_as_string(0, 1).contains("| cube |"));
+	}
+
+	#[test] fn test_matrix_invalid() {
+		assert!(pearson_matrix(&RkgTabN::from_flat(&[1.0, 2.0], &[2])).is_none());
+
+		// a constant collumn has no correlation, and no labels are made up
+		let matrix = pearson_matrix(&RkgTabN::from_flat(&[1, 5, 2, 5, 3, 5], &[3, 2])).unwrap();
+		assert!(matrix.get(&[0, 1]).is_nan());
+		assert_eq!(matrix.labels_by_axis(0), None);
+	}
+	// endregion:
+}
@@ -0,0 +1,231 @@
+use std::fmt::Display;
+
+use crate::math::datatypes::rkgtab::RkgTabN;
+
+// The functions over slices return None when the statistic does not exist
+// for the data, the table methods put NaN in those places instead so the
+// result keeps the shape of the table.
+
+// MARK: Slice statistics
+
+/// the arithmetic mean, None if data is empty
+pub fn mean(data: &[f64]) -> Option<f64> {
+	if data.is_empty() { return None }
+	Some(data.iter().sum::<f64>() / data.len() as f64)
+}
+
+/// the middle value, or the mean of the two middle values, None if data is empty
+pub fn median(data: &[f64]) -> Option<f64> {
+	quantile(data, 0.5)
+}
+
+/// the most common value, the smallest one if there is a tie
+/// None if data is empty
+pub fn mode(data: &[f64]) -> Option<f64> {
+	let sorted = sorted(data);
+	let mut best: Option<(f64, usize)> = None;
+
+	for run in sorted.chunk_by(|a, b| a == b) {
+		if best.is_none_or(|(_, count)| run.len() > count) { best = Some((run[0], run.len())); }
+	}
+
+	best.map(|(value, _)| value)
+}
+
+/// the sample variance, divides by n - 1, None if data has less than two values
+pub fn variance(data: &[f64]) -> Option<f64> {
+	if data.len() < 2 { return None }
+	Some(central_moment(data, 2) * data.len() as f64 / (data.len() - 1) as f64)
+}
+
+/// the sample standard deviation, the square root of the sample variance
+pub fn standard_deviation(data: &[f64]) -> Option<f64> {
+	variance(data).map(f64::sqrt)
+}
+
+/// the smallest value, None if data is empty
+pub fn min(data: &[f64]) -> Option<f64> {
+	data.iter().copied().reduce(f64::min)
+}
+
+/// the largest value, None if data is empty
+pub fn max(data: &[f64]) -> Option<f64> {
+	data.iter().copied().reduce(f64::max)
+}
+
+/// the value below which q of the data falls, interpolating linearly
+/// between the closest ranks
+/// None if data is empty or q is not in [0, 1]
+pub fn quantile(data: &[f64], q: f64) -> Option<f64> {
+	if data.is_empty() || !(0.0..=1.0).contains(&q) { return None }
+
+	let sorted = sorted(data);
+	let rank = q * (sorted.len() - 1) as f64;
+	let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+	Some(sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64))
+}
+
+/// the moment coefficient of skewness, m3 / m2^(3/2)
+/// None if data is empty or every value is the same
+pub fn skewness(data: &[f64]) -> Option<f64> {
+	let m2 = central_moment(data, 2);
+	if data.is_empty() || m2 == 0.0 { return None }
+	Some(central_moment(data, 3) / m2.powf(1.5))
+}
+
+/// the excess kurtosis, m4 / m2^2 - 3, so a normal distribution has zero
+/// None if data is empty or every value is the same
+pub fn kurtosis(data: &[f64]) -> Option<f64> {
+	let m2 = central_moment(data, 2);
+	if data.is_empty() || m2 == 0.0 { return None }
+	Some(central_moment(data, 4) / (m2 * m2) - 3.0)
+}
+
+// the k-th moment about the mean, dividing by n
+fn central_moment(data: &[f64], k: i32) -> f64 {
+	let Some(mean) = mean(data) else { return 0.0 };
+	data.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / data.len() as f64
+}
+
+fn sorted(data: &[f64]) -> Vec<f64> {
+	let mut sorted = data.to_vec();
+	sorted.sort_by(f64::total_cmp);
+	sorted
+}
+
+// MARK: TDescriptive
+
+/// Implements descriptive statistics along an axis of a table
+///
+/// Every method removes axis from the table and keeps the labels of the
+/// other axes, so the statistic for a collumn stays under its label.  They
+/// panic if axis is out of bounds.
+pub trait TDescriptive {
+	/// returns the table of f applied to the values along axis, None becomes NaN
+	fn reduce(&self, axis: usize, f: impl Fn(&[f64]) -> Option<f64>) -> RkgTabN<f64>;
+
+	/// the mean along axis
+	fn mean(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, mean) }
+
+	/// the median along axis
+	fn median(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, median) }
+
+	/// the most common value along axis
+	fn mode(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, mode) }
+
+	/// the sample variance along axis
+	fn variance(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, variance) }
+
+	/// the sample standard deviation along axis
+	fn standard_deviation(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, standard_deviation) }
+
+	/// the smallest value along axis
+	fn min(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, min) }
+
+	/// the largest value along axis
+	fn max(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, max) }
+
+	/// the q quantile along axis, None if q is not in [0, 1]
+	fn quantile(&self, axis: usize, q: f64) -> Option<RkgTabN<f64>> {
+		if !(0.0..=1.0).contains(&q) { return None }
+		Some(self.reduce(axis, |data| quantile(data, q)))
+	}
+
+	/// the skewness along axis
+	fn skewness(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, skewness) }
+
+	/// the excess kurtosis along axis
+	fn kurtosis(&self, axis: usize) -> RkgTabN<f64> { self.reduce(axis, kurtosis) }
+}
+
+impl<T: Clone + Copy + Display + Into<f64>> TDescriptive for RkgTabN<T> {
+	fn reduce(&self, axis: usize, f: impl Fn(&[f64]) -> Option<f64>) -> RkgTabN<f64> {
+		self.reduce_axis(axis, |lane| {
+			let values: Vec<f64> = lane.iter().map(|x| (*x).into()).collect();
+			f(&values).unwrap_or(f64::NAN)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(actual: f64, expected: f64) {
+		assert!((actual - expected).abs() <= 1e-12, "expected {} but got {}", expected, actual);
+	}
+
+	const DATA: [f64; 8] = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+	// region: test_slice
+	#[test] fn test_location() {
+		assert_eq!(mean(&DATA), Some(5.0));
+		assert_eq!(median(&DATA), Some(4.5));
+		assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+		assert_eq!(mode(&DATA), Some(4.0));
+		assert_eq!(mode(&[3.0, 1.0, 3.0, 1.0]), Some(1.0));
+		assert_eq!(min(&DATA), Some(2.0));
+		assert_eq!(max(&DATA), Some(9.0));
+	}
+
+	#[test] fn test_spread() {
+		assert_close(variance(&DATA).unwrap(), 32.0 / 7.0);
+		assert_close(standard_deviation(&DATA).unwrap(), (32.0f64 / 7.0).sqrt());
+		assert_eq!(quantile(&DATA, 0.0), Some(2.0));
+		assert_eq!(quantile(&DATA, 1.0), Some(9.0));
+		assert_close(quantile(&DATA, 0.25).unwrap(), 4.0);
+		assert_close(quantile(&[1.0, 2.0, 3.0, 4.0], 0.75).unwrap(), 3.25);
+	}
+
+	#[test] fn test_shape() {
+		assert_close(skewness(&DATA).unwrap(), 0.65625);
+		assert_close(kurtosis(&DATA).unwrap(), 2.78125 - 3.0);
+		assert_close(skewness(&[1.0, 2.0, 3.0]).unwrap(), 0.0);
+	}
+
+	#[test] fn test_invalid() {
+		assert_eq!(mean(&[]), None);
+		assert_eq!(mode(&[]), None);
+		assert_eq!(min(&[]), None);
+		assert_eq!(variance(&[1.0]), None);
+		assert_eq!(quantile(&DATA, 1.5), None);
+		assert_eq!(skewness(&[2.0, 2.0]), None);
+		assert_eq!(kurtosis(&[]), None);
+	}
+	// endregion:
+
+	// region: test_table
+	#[test] fn test_table_collumns() {
+		let mut table = RkgTabN::from_flat(&[1, 10, 2, 20, 3, 30, 4, 40], &[4, 2]);
+		table.set_labels_from_flat(&["small".to_string(), "large".to_string()], &[1]);
+
+		let means = table.mean(0);
+		assert_eq!(means.shape(), vec![2]);
+		assert_eq!(means.get(&[0]), 2.5);
+		assert_eq!(means.get(&[1]), 25.0);
+		assert_eq!(means.get_label_by_axis(0, 1), "large");
+
+		assert_eq!(table.max(0).get(&[1]), 40.0);
+		assert_close(table.variance(0).get(&[0]), 5.0 / 3.0);
+		assert_close(table.quantile(0, 0.5).unwrap().get(&[1]), 25.0);
+		assert!(table.quantile(0, -0.1).is_none());
+	}
+
+	#[test] fn test_table_rows() {
+		let table = RkgTabN::from_flat(&[1.0, 3.0, 2.0, 2.0], &[2, 2]);
+		let spread = table.standard_deviation(1);
+		assert_eq!(spread.shape(), vec![2]);
+		assert_close(spread.get(&[0]), 2.0f64.sqrt());
+		assert_eq!(spread.get(&[1]), 0.0);
+		assert!(table.skewness(1).get(&[1]).is_nan());
+	}
+
+	#[test] fn test_table_3d() {
+		let table = RkgTabN::from_flat(&[1, 2, 3, 4, 5, 6, 7, 8], &[2, 2, 2]);
+		let medians = table.median(1);
+		assert_eq!(medians.shape(), vec![2, 2]);
+		assert_eq!(medians.get(&[0, 0]), 2.0);
+		assert_eq!(medians.get(&[1, 1]), 7.0);
+	}
+	// endregion:
+}
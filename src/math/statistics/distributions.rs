@@ -0,0 +1,1064 @@
+use std::f64::consts::{PI, SQRT_2};
+
+use rand::Rng;
+
+// Every constructor returns None when the parameters do not describe a
+// distribution, so the methods never have to check them again.  Quantiles
+// return None when p is not in [0, 1].
+
+// MARK: Special functions
+
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS: [f64; 9] = [
+	0.999_999_999_999_809_9,
+	676.520_368_121_885_1,
+	-1_259.139_216_722_402_8,
+	771.323_428_777_653_1,
+	-176.615_029_162_140_6,
+	12.507_343_278_686_905,
+	-0.138_571_095_265_720_12,
+	9.984_369_578_019_572e-6,
+	1.505_632_735_149_311_6e-7,
+];
+
+// relative accuracy the series and continued fractions stop at
+const EPSILON: f64 = 1e-15;
+// stands in for zero in the continued fractions to avoid dividing by it
+const TINY: f64 = 1e-300;
+const MAX_ITERATIONS: usize = 1000;
+
+/// Natural log of the gamma function for x > 0, using the Lanczos approximation
+pub fn ln_gamma(x: f64) -> f64 {
+	// reflection formula keeps the approximation in its accurate range
+	if x < 0.5 { return (PI / (PI * x).sin()).abs().ln() - ln_gamma(1.0 - x) }
+
+	let x = x - 1.0;
+	let t = x + LANCZOS_G + 0.5;
+	let sum = LANCZOS[1..].iter()
+		.enumerate()
+		.fold(LANCZOS[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.0));
+
+	0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized lower incomplete gamma function P(a, x) for a > 0 and x >= 0
+pub fn gamma_p(a: f64, x: f64) -> f64 {
+	if x <= 0.0 { return 0.0 }
+	if x < a + 1.0 { gamma_series(a, x) } else { 1.0 - gamma_continued_fraction(a, x) }
+}
+
+/// Regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x)
+pub fn gamma_q(a: f64, x: f64) -> f64 {
+	if x <= 0.0 { return 1.0 }
+	if x < a + 1.0 { 1.0 - gamma_series(a, x) } else { gamma_continued_fraction(a, x) }
+}
+
+// P(a, x) as a power series, converges quickly for x < a + 1
+fn gamma_series(a: f64, x: f64) -> f64 {
+	let mut term = 1.0 / a;
+	let mut sum = term;
+	let mut ap = a;
+
+	for _ in 0..MAX_ITERATIONS {
+		ap += 1.0;
+		term *= x / ap;
+		sum += term;
+		if term.abs() < sum.abs() * EPSILON { break; }
+	}
+
+	sum * (a * x.ln() - x - ln_gamma(a)).exp()
+}
+
+// Q(a, x) as a continued fraction evaluated with Lentz's method, for x >= a + 1
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+	let mut b = x + 1.0 - a;
+	let mut c = 1.0 / TINY;
+	let mut d = 1.0 / b;
+	let mut h = d;
+
+	for i in 1..MAX_ITERATIONS {
+		let an = -(i as f64) * (i as f64 - a);
+		b += 2.0;
+		d = an * d + b;
+		if d.abs() < TINY { d = TINY; }
+		c = b + an / c;
+		if c.abs() < TINY { c = TINY; }
+		d = 1.0 / d;
+		let delta = d * c;
+		h *= delta;
+		if (delta - 1.0).abs() < EPSILON { break; }
+	}
+
+	(a * x.ln() - x - ln_gamma(a)).exp() * h
+}
+
+/// Regularized incomplete beta function I_x(a, b) for a, b > 0 and x in [0, 1]
+pub fn beta_regularized(a: f64, b: f64, x: f64) -> f64 {
+	if x <= 0.0 { return 0.0 }
+	if x >= 1.0 { return 1.0 }
+
+	let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (-x).ln_1p()).exp();
+	// the continued fraction converges quickly below the mean, use symmetry above it
+	if x < (a + 1.0) / (a + b + 2.0) {
+		front * beta_continued_fraction(a, b, x) / a
+	}
+	else {
+		1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+	}
+}
+
+// continued fraction for I_x(a, b) evaluated with Lentz's method
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+	let mut c = 1.0;
+	let mut d = 1.0 - (a + b) * x / (a + 1.0);
+	if d.abs() < TINY { d = TINY; }
+	d = 1.0 / d;
+	let mut h = d;
+
+	for i in 1..MAX_ITERATIONS {
+		let m = i as f64;
+		// every step has an even and an odd term
+		let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+		let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+
+		let mut delta = 1.0;
+		for an in [even, odd] {
+			d = 1.0 + an * d;
+			if d.abs() < TINY { d = TINY; }
+			c = 1.0 + an / c;
+			if c.abs() < TINY { c = TINY; }
+			d = 1.0 / d;
+			delta = d * c;
+			h *= delta;
+		}
+		if (delta - 1.0).abs() < EPSILON { break; }
+	}
+
+	h
+}
+
+/// The error function erf(x)
+pub fn erf(x: f64) -> f64 {
+	let p = gamma_p(0.5, x * x);
+	if x < 0.0 { -p } else { p }
+}
+
+/// The complementary error function 1 - erf(x), accurate far into the tails
+pub fn erfc(x: f64) -> f64 {
+	if x < 0.0 { 1.0 + gamma_p(0.5, x * x) } else { gamma_q(0.5, x * x) }
+}
+
+// nCr as an f64 from the running product C(n - r + i, i), each step is a
+// whole number so it is exact below 2^53 and infinite once it overflows
+fn choose(n: u32, r: u32) -> f64 {
+	let r = r.min(n - r);
+	(1..=r).fold(1.0, |acc, i| acc * (n - r + i) as f64 / i as f64)
+}
+
+fn ln_choose(n: u32, r: u32) -> f64 {
+	ln_gamma(n as f64 + 1.0) - ln_gamma(r as f64 + 1.0) - ln_gamma((n - r) as f64 + 1.0)
+}
+
+// MARK: TDiscreteDistribution
+
+/// Functions shared by distributions over the whole numbers
+pub trait TDiscreteDistribution {
+	/// The smallest value with non zero probubility, and the largest if there is one
+	fn support(&self) -> (u64, Option<u64>);
+
+	/// Probubility of exactly k
+	fn pmf(&self, k: u64) -> f64;
+
+	/// Expected value
+	fn mean(&self) -> f64;
+
+	/// Variance
+	fn variance(&self) -> f64;
+
+	/// Probubility of k or less
+	fn cdf(&self, k: u64) -> f64 {
+		let (start, end) = self.support();
+		if k < start { return 0.0 }
+
+		let last = end.map_or(k, |end| k.min(end));
+		(start..=last).map(|i| self.pmf(i)).sum::<f64>().min(1.0)
+	}
+
+	/// The smallest k with cdf(k) >= p
+	fn quantile(&self, p: f64) -> Option<u64> {
+		if !(0.0..=1.0).contains(&p) { return None }
+
+		let (start, end) = self.support();
+		let mut total = 0.0;
+		let mut k = start;
+		loop {
+			let mass = self.pmf(k);
+			total += mass;
+			if total >= p || Some(k) == end { return Some(k) }
+			// the rest of the mass was lost to rounding
+			if mass == 0.0 && k as f64 > self.mean() { return Some(k) }
+			k += 1;
+		}
+	}
+
+	/// Draws a value using inverse transform sampling
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+		self.quantile(rng.random::<f64>()).unwrap_or(0)
+	}
+}
+
+// MARK: TContinuousDistribution
+
+/// Functions shared by distributions over the real numbers
+pub trait TContinuousDistribution {
+	/// Probubility density at x
+	fn pdf(&self, x: f64) -> f64;
+
+	/// Probubility of x or less
+	fn cdf(&self, x: f64) -> f64;
+
+	/// The x with cdf(x) = p
+	fn quantile(&self, p: f64) -> Option<f64>;
+
+	/// Expected value
+	fn mean(&self) -> f64;
+
+	/// Variance
+	fn variance(&self) -> f64;
+
+	/// Draws a value using inverse transform sampling
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+		self.quantile(rng.random::<f64>()).unwrap_or(f64::NAN)
+	}
+}
+
+fn is_probubility(p: f64) -> bool {
+	(0.0..=1.0).contains(&p)
+}
+
+// MARK: Bernoulli
+
+/// A single trial that is 1 with probubility p and 0 otherwise
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bernoulli {
+	p: f64,
+}
+
+impl Bernoulli {
+	/// returns None if p is not in [0, 1]
+	pub fn new(p: f64) -> Option<Self> {
+		is_probubility(p).then_some(Self { p })
+	}
+}
+
+impl TDiscreteDistribution for Bernoulli {
+	fn support(&self) -> (u64, Option<u64>) {
+		(0, Some(1))
+	}
+
+	fn pmf(&self, k: u64) -> f64 {
+		match k {
+			0 => 1.0 - self.p,
+			1 => self.p,
+			_ => 0.0
+		}
+	}
+
+	fn mean(&self) -> f64 {
+		self.p
+	}
+
+	fn variance(&self) -> f64 {
+		self.p * (1.0 - self.p)
+	}
+
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+		(rng.random::<f64>() < self.p) as u64
+	}
+}
+
+// MARK: Binomial
+
+/// Number of successes in n independent trials that each succeed with probubility p
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Binomial {
+	n: u32,
+	p: f64,
+}
+
+impl Binomial {
+	/// returns None if p is not in [0, 1]
+	pub fn new(n: u32, p: f64) -> Option<Self> {
+		is_probubility(p).then_some(Self { n, p })
+	}
+}
+
+impl TDiscreteDistribution for Binomial {
+	fn support(&self) -> (u64, Option<u64>) {
+		(0, Some(self.n as u64))
+	}
+
+	fn pmf(&self, k: u64) -> f64 {
+		if k > self.n as u64 { return 0.0 }
+		let k = k as u32;
+
+		let ways = choose(self.n, k);
+		if ways.is_finite() {
+			return ways * self.p.powi(k as i32) * (1.0 - self.p).powi((self.n - k) as i32)
+		}
+
+		// nCk overflows an f64 so work with logs
+		(ln_choose(self.n, k) + k as f64 * self.p.ln() + (self.n - k) as f64 * (1.0 - self.p).ln()).exp()
+	}
+
+	fn mean(&self) -> f64 {
+		self.n as f64 * self.p
+	}
+
+	fn variance(&self) -> f64 {
+		self.n as f64 * self.p * (1.0 - self.p)
+	}
+
+	// running the trials is cheaper than summing the pmf for every draw
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+		(0..self.n).filter(|_| rng.random::<f64>() < self.p).count() as u64
+	}
+}
+
+// MARK: Geometric
+
+/// Number of trials up to and including the first success, each trial
+/// succeeding with probubility p.  The support starts at 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Geometric {
+	p: f64,
+}
+
+impl Geometric {
+	/// returns None if p is not in (0, 1]
+	pub fn new(p: f64) -> Option<Self> {
+		(p > 0.0 && p <= 1.0).then_some(Self { p })
+	}
+}
+
+impl TDiscreteDistribution for Geometric {
+	fn support(&self) -> (u64, Option<u64>) {
+		(1, None)
+	}
+
+	fn pmf(&self, k: u64) -> f64 {
+		if k == 0 { return 0.0 }
+		(1.0 - self.p).powf((k - 1) as f64) * self.p
+	}
+
+	fn cdf(&self, k: u64) -> f64 {
+		1.0 - (1.0 - self.p).powf(k as f64)
+	}
+
+	fn quantile(&self, p: f64) -> Option<u64> {
+		if !is_probubility(p) { return None }
+		if p == 0.0 || self.p == 1.0 { return Some(1) }
+		if p == 1.0 { return Some(u64::MAX) }
+
+		let k = ((1.0 - p).ln() / (1.0 - self.p).ln()).ceil().max(1.0) as u64;
+		// step back if rounding put k one past the answer
+		if k > 1 && self.cdf(k - 1) >= p { Some(k - 1) } else { Some(k) }
+	}
+
+	fn mean(&self) -> f64 {
+		1.0 / self.p
+	}
+
+	fn variance(&self) -> f64 {
+		(1.0 - self.p) / (self.p * self.p)
+	}
+}
+
+// MARK: Poisson
+
+/// Number of events in an interval where they happen independently at rate lambda
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Poisson {
+	lambda: f64,
+}
+
+impl Poisson {
+	/// returns None if lambda is not positive and finite
+	pub fn new(lambda: f64) -> Option<Self> {
+		(lambda > 0.0 && lambda.is_finite()).then_some(Self { lambda })
+	}
+}
+
+impl TDiscreteDistribution for Poisson {
+	fn support(&self) -> (u64, Option<u64>) {
+		(0, None)
+	}
+
+	fn pmf(&self, k: u64) -> f64 {
+		let k = k as f64;
+		(k * self.lambda.ln() - self.lambda - ln_gamma(k + 1.0)).exp()
+	}
+
+	fn cdf(&self, k: u64) -> f64 {
+		gamma_q(k as f64 + 1.0, self.lambda)
+	}
+
+	fn mean(&self) -> f64 {
+		self.lambda
+	}
+
+	fn variance(&self) -> f64 {
+		self.lambda
+	}
+}
+
+// MARK: Hypergeometric
+
+/// Number of successes in draws taken without replacement from a population
+/// that contains a given number of successes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hypergeometric {
+	population: u32,
+	successes: u32,
+	draws: u32,
+}
+
+impl Hypergeometric {
+	/// returns None if there are more successes or draws than the population
+	pub fn new(population: u32, successes: u32, draws: u32) -> Option<Self> {
+		(successes <= population && draws <= population).then_some(Self { population, successes, draws })
+	}
+}
+
+impl TDiscreteDistribution for Hypergeometric {
+	fn support(&self) -> (u64, Option<u64>) {
+		let start = (self.draws + self.successes).saturating_sub(self.population);
+		(start as u64, Some(self.draws.min(self.successes) as u64))
+	}
+
+	fn pmf(&self, k: u64) -> f64 {
+		let (start, end) = self.support();
+		if k < start || k > end.unwrap_or(0) { return 0.0 }
+		let k = k as u32;
+
+		let failures = self.population - self.successes;
+		let (a, b, c) = (
+			choose(self.successes, k),
+			choose(failures, self.draws - k),
+			choose(self.population, self.draws)
+		);
+		if a.is_finite() && b.is_finite() && c.is_finite() { return a * b / c }
+
+		(ln_choose(self.successes, k) + ln_choose(failures, self.draws - k) - ln_choose(self.population, self.draws)).exp()
+	}
+
+	fn mean(&self) -> f64 {
+		self.draws as f64 * self.successes as f64 / self.population as f64
+	}
+
+	fn variance(&self) -> f64 {
+		// a single item is always drawn the same way
+		if self.population <= 1 { return 0.0 }
+
+		let n = self.population as f64;
+		let share = self.successes as f64 / n;
+		self.draws as f64 * share * (1.0 - share) * (n - self.draws as f64) / (n - 1.0)
+	}
+
+	// draws one at a time, cheaper than summing the pmf for every draw
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+		let (mut left, mut successes_left) = (self.population, self.successes);
+		let mut ret_val = 0;
+
+		for _ in 0..self.draws {
+			if rng.random_range(0..left) < successes_left {
+				successes_left -= 1;
+				ret_val += 1;
+			}
+			left -= 1;
+		}
+
+		ret_val
+	}
+}
+
+// MARK: Uniform
+
+/// Every value between a and b is equally likely
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform {
+	a: f64,
+	b: f64,
+}
+
+impl Uniform {
+	/// returns None unless a < b and both are finite
+	pub fn new(a: f64, b: f64) -> Option<Self> {
+		(a < b && a.is_finite() && b.is_finite()).then_some(Self { a, b })
+	}
+}
+
+impl TContinuousDistribution for Uniform {
+	fn pdf(&self, x: f64) -> f64 {
+		if x < self.a || x > self.b { 0.0 } else { 1.0 / (self.b - self.a) }
+	}
+
+	fn cdf(&self, x: f64) -> f64 {
+		((x - self.a) / (self.b - self.a)).clamp(0.0, 1.0)
+	}
+
+	fn quantile(&self, p: f64) -> Option<f64> {
+		is_probubility(p).then_some(self.a + p * (self.b - self.a))
+	}
+
+	fn mean(&self) -> f64 {
+		(self.a + self.b) / 2.0
+	}
+
+	fn variance(&self) -> f64 {
+		(self.b - self.a).powi(2) / 12.0
+	}
+}
+
+// MARK: Normal
+
+/// The normal distribution with a mean and standard deviation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normal {
+	mean: f64,
+	sd: f64,
+}
+
+impl Normal {
+	/// returns None unless sd is positive and both are finite
+	pub fn new(mean: f64, sd: f64) -> Option<Self> {
+		(sd > 0.0 && sd.is_finite() && mean.is_finite()).then_some(Self { mean, sd })
+	}
+
+	/// The standard normal distribution, mean 0 and standard deviation 1
+	pub fn standard() -> Self {
+		Self { mean: 0.0, sd: 1.0 }
+	}
+}
+
+// Acklam's rational approximation of the standard normal quantile
+const ACKLAM_A: [f64; 6] = [
+	-3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2,
+	1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239,
+];
+const ACKLAM_B: [f64; 5] = [
+	-5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2,
+	6.680_131_188_771_972e1, -1.328_068_155_288_572e1,
+];
+const ACKLAM_C: [f64; 6] = [
+	-7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838,
+	-2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783,
+];
+const ACKLAM_D: [f64; 4] = [
+	7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996, 3.754_408_661_907_416,
+];
+
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+	coefficients.iter().fold(0.0, |acc, c| acc * x + c)
+}
+
+fn standard_normal_cdf(z: f64) -> f64 {
+	0.5 * erfc(-z / SQRT_2)
+}
+
+fn standard_normal_quantile(p: f64) -> f64 {
+	if p == 0.0 { return f64::NEG_INFINITY }
+	if p == 1.0 { return f64::INFINITY }
+
+	let low = 0.02425;
+	let tail = |q: f64| polynomial(&ACKLAM_C, q) / (polynomial(&ACKLAM_D, q) * q + 1.0);
+	let x = if p < low {
+		tail((-2.0 * p.ln()).sqrt())
+	}
+	else if p > 1.0 - low {
+		-tail((-2.0 * (1.0 - p).ln()).sqrt())
+	}
+	else {
+		let q = p - 0.5;
+		let r = q * q;
+		polynomial(&ACKLAM_A, r) * q / (polynomial(&ACKLAM_B, r) * r + 1.0)
+	};
+
+	// one step of Halley's method brings it to full precision
+	let e = standard_normal_cdf(x) - p;
+	let u = e * (2.0 * PI).sqrt() * (x * x / 2.0).exp();
+	x - u / (1.0 + x * u / 2.0)
+}
+
+// Box-Muller transform, 1 - random keeps the log away from zero
+fn standard_normal_sample<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+	let u1 = 1.0 - rng.random::<f64>();
+	let u2 = rng.random::<f64>();
+	(-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+impl TContinuousDistribution for Normal {
+	fn pdf(&self, x: f64) -> f64 {
+		let z = (x - self.mean) / self.sd;
+		(-0.5 * z * z).exp() / (self.sd * (2.0 * PI).sqrt())
+	}
+
+	fn cdf(&self, x: f64) -> f64 {
+		standard_normal_cdf((x - self.mean) / self.sd)
+	}
+
+	fn quantile(&self, p: f64) -> Option<f64> {
+		is_probubility(p).then(|| self.mean + self.sd * standard_normal_quantile(p))
+	}
+
+	fn mean(&self) -> f64 {
+		self.mean
+	}
+
+	fn variance(&self) -> f64 {
+		self.sd * self.sd
+	}
+
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+		self.mean + self.sd * standard_normal_sample(rng)
+	}
+}
+
+// MARK: Exponential
+
+/// Time between events that happen independently at a rate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exponential {
+	rate: f64,
+}
+
+impl Exponential {
+	/// returns None if rate is not positive and finite
+	pub fn new(rate: f64) -> Option<Self> {
+		(rate > 0.0 && rate.is_finite()).then_some(Self { rate })
+	}
+}
+
+impl TContinuousDistribution for Exponential {
+	fn pdf(&self, x: f64) -> f64 {
+		if x < 0.0 { 0.0 } else { self.rate * (-self.rate * x).exp() }
+	}
+
+	fn cdf(&self, x: f64) -> f64 {
+		if x < 0.0 { 0.0 } else { -(-self.rate * x).exp_m1() }
+	}
+
+	fn quantile(&self, p: f64) -> Option<f64> {
+		is_probubility(p).then(|| -(-p).ln_1p() / self.rate)
+	}
+
+	fn mean(&self) -> f64 {
+		1.0 / self.rate
+	}
+
+	fn variance(&self) -> f64 {
+		1.0 / (self.rate * self.rate)
+	}
+}
+
+// MARK: Gamma
+
+/// The gamma distribution with a shape and scale.  A shape of df/2 and scale
+/// of 2 is the chi-square distribution with df degrees of freedom
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gamma {
+	shape: f64,
+	scale: f64,
+}
+
+impl Gamma {
+	/// returns None unless shape and scale are positive and finite
+	pub fn new(shape: f64, scale: f64) -> Option<Self> {
+		let valid = shape > 0.0 && scale > 0.0 && shape.is_finite() && scale.is_finite();
+		valid.then_some(Self { shape, scale })
+	}
+
+	/// The chi-square distribution with df degrees of freedom
+	/// returns None unless df is positive and finite
+	pub fn chi_square(df: f64) -> Option<Self> {
+		Self::new(df / 2.0, 2.0)
+	}
+}
+
+impl TContinuousDistribution for Gamma {
+	fn pdf(&self, x: f64) -> f64 {
+		if x < 0.0 { return 0.0 }
+		if x == 0.0 {
+			if self.shape < 1.0 { return f64::INFINITY }
+			return if self.shape == 1.0 { 1.0 / self.scale } else { 0.0 }
+		}
+
+		let ln_pdf = (self.shape - 1.0) * x.ln() - x / self.scale
+			- ln_gamma(self.shape) - self.shape * self.scale.ln();
+		ln_pdf.exp()
+	}
+
+	fn cdf(&self, x: f64) -> f64 {
+		gamma_p(self.shape, x / self.scale)
+	}
+
+	fn quantile(&self, p: f64) -> Option<f64> {
+		if !is_probubility(p) { return None }
+		if p == 0.0 { return Some(0.0) }
+		if p == 1.0 { return Some(f64::INFINITY) }
+
+		// bracket the answer then bisect, the cdf is increasing
+		let mut high = self.mean().max(1.0);
+		while self.cdf(high) < p { high *= 2.0; }
+		let mut low = 0.0;
+
+		for _ in 0..MAX_ITERATIONS {
+			let middle = (low + high) / 2.0;
+			if self.cdf(middle) < p { low = middle; } else { high = middle; }
+			if high - low <= EPSILON * high { break; }
+		}
+
+		Some((low + high) / 2.0)
+	}
+
+	fn mean(&self) -> f64 {
+		self.shape * self.scale
+	}
+
+	fn variance(&self) -> f64 {
+		self.shape * self.scale * self.scale
+	}
+
+	// Marsaglia and Tsang's method
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+		if self.shape < 1.0 {
+			// boost the shape above one and scale the result back down
+			let boosted = Gamma { shape: self.shape + 1.0, scale: self.scale };
+			let u = 1.0 - rng.random::<f64>();
+			return boosted.sample(rng) * u.powf(1.0 / self.shape)
+		}
+
+		let d = self.shape - 1.0 / 3.0;
+		let c = 1.0 / (9.0 * d).sqrt();
+		loop {
+			let x = standard_normal_sample(rng);
+			let v = (1.0 + c * x).powi(3);
+			if v <= 0.0 { continue; }
+
+			let u = 1.0 - rng.random::<f64>();
+			if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+				return d * v * self.scale
+			}
+		}
+	}
+}
+
+// MARK: StudentT
+
+/// Student's t distribution with df degrees of freedom, the distribution of
+/// a sample mean measured in estimated standard errors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StudentT {
+	df: f64,
+}
+
+impl StudentT {
+	/// returns None if df is not positive
+	pub fn new(df: f64) -> Option<Self> {
+		(df > 0.0 && !df.is_nan()).then_some(Self { df })
+	}
+
+	// P(T <= -|t|), found from the beta function so the tails keep their precision
+	fn tail(&self, t: f64) -> f64 {
+		0.5 * beta_regularized(self.df / 2.0, 0.5, self.df / (self.df + t * t))
+	}
+}
+
+impl TContinuousDistribution for StudentT {
+	fn pdf(&self, x: f64) -> f64 {
+		let v = self.df;
+		let ln_pdf = ln_gamma((v + 1.0) / 2.0) - ln_gamma(v / 2.0) - 0.5 * (v * PI).ln()
+			- (v + 1.0) / 2.0 * (x * x / v).ln_1p();
+		ln_pdf.exp()
+	}
+
+	fn cdf(&self, x: f64) -> f64 {
+		if x > 0.0 { 1.0 - self.tail(x) } else { self.tail(x) }
+	}
+
+	fn quantile(&self, p: f64) -> Option<f64> {
+		if !is_probubility(p) { return None }
+		if p == 0.0 { return Some(f64::NEG_INFINITY) }
+		if p == 1.0 { return Some(f64::INFINITY) }
+		if p == 0.5 { return Some(0.0) }
+
+		// find |t| from the smaller tail by bisection, then give it the sign of p - 0.5
+		let q = p.min(1.0 - p);
+		let mut high = 1.0;
+		while self.tail(high) > q { high *= 2.0; }
+		let mut low = 0.0;
+
+		for _ in 0..MAX_ITERATIONS {
+			let middle = (low + high) / 2.0;
+			if self.tail(middle) > q { low = middle; } else { high = middle; }
+			if high - low <= EPSILON * high { break; }
+		}
+
+		let t = (low + high) / 2.0;
+		Some(if p < 0.5 { -t } else { t })
+	}
+
+	/// NaN when df is 1 or less
+	fn mean(&self) -> f64 {
+		if self.df > 1.0 { 0.0 } else { f64::NAN }
+	}
+
+	/// infinite when df is in (1, 2], NaN when df is 1 or less
+	fn variance(&self) -> f64 {
+		if self.df > 2.0 { self.df / (self.df - 2.0) }
+		else if self.df > 1.0 { f64::INFINITY }
+		else { f64::NAN }
+	}
+
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+		let chi = Gamma { shape: self.df / 2.0, scale: 2.0 };
+		standard_normal_sample(rng) / (chi.sample(rng) / self.df).sqrt()
+	}
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand::rngs::StdRng;
+
+	fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+		assert!(
+			(actual - expected).abs() <= tolerance,
+			"expected {} but got {}", expected, actual
+		);
+	}
+
+// region: test_special_functions
+	#[test] fn test_ln_gamma() {
+		assert_close(ln_gamma(1.0), 0.0, 1e-14);
+		assert_close(ln_gamma(5.0), 24f64.ln(), 1e-13);
+		assert_close(ln_gamma(0.5), PI.sqrt().ln(), 1e-14);
+		assert_close(ln_gamma(101.0), 363.73937555556347, 1e-10);
+	}
+
+	#[test] fn test_gamma_p() {
+		assert_close(gamma_p(1.0, 2.0), 1.0 - (-2f64).exp(), 1e-15);
+		assert_close(gamma_p(2.0, 1.0), 1.0 - 2.0 * (-1f64).exp(), 1e-15);
+		assert_close(gamma_p(3.0, 10.0) + gamma_q(3.0, 10.0), 1.0, 1e-15);
+		assert_eq!(gamma_p(2.0, 0.0), 0.0);
+	}
+
+	#[test] fn test_erf() {
+		assert_close(erf(0.0), 0.0, 1e-15);
+		assert_close(erf(1.0), 0.8427007929497149, 1e-15);
+		assert_close(erf(-0.5), -0.5204998778130465, 1e-15);
+		assert_close(erfc(3.0), 2.209049699858544e-05, 1e-18);
+		assert_close(erfc(-1.0), 1.8427007929497148, 1e-15);
+	}
+
+	#[test] fn test_beta_regularized() {
+		assert_close(beta_regularized(2.0, 3.0, 0.5), 0.6875, 1e-15);
+		assert_close(beta_regularized(0.5, 10.0, 0.01), 0.3420718248432155, 1e-14);
+		assert_close(beta_regularized(30.0, 40.0, 0.6), 0.998074111202461, 1e-14);
+		assert_eq!((beta_regularized(2.0, 2.0, 0.0), beta_regularized(2.0, 2.0, 1.0)), (0.0, 1.0));
+	}
+// endregion:
+
+// region: test_discrete
+	#[test] fn test_bernoulli() {
+		let d = Bernoulli::new(0.3).unwrap();
+		assert_close(d.pmf(1), 0.3, 1e-15);
+		assert_close(d.cdf(0), 0.7, 1e-15);
+		assert_eq!(d.quantile(0.7), Some(0));
+		assert_eq!(d.quantile(0.71), Some(1));
+		assert_close(d.variance(), 0.21, 1e-15);
+		assert!(Bernoulli::new(1.5).is_none());
+	}
+
+	#[test] fn test_binomial() {
+		let d = Binomial::new(10, 0.5).unwrap();
+		assert_close(d.pmf(5), 252.0 / 1024.0, 1e-15);
+		assert_close(d.cdf(5), 638.0 / 1024.0, 1e-15);
+		assert_eq!(d.pmf(11), 0.0);
+		assert_eq!(d.quantile(0.5), Some(5));
+		assert_eq!(d.quantile(1.0), Some(10));
+		assert_eq!(d.quantile(1.5), None);
+		assert_eq!((d.mean(), d.variance()), (5.0, 2.5));
+	}
+
+	#[test] fn test_binomial_large() {
+		// 2000 C 1000 does not fit in an f64
+		let d = Binomial::new(2000, 0.5).unwrap();
+		assert_close(d.pmf(1000), 0.01783901114585432, 1e-12);
+		// 60 C 30 is above 2^53
+		assert_close(Binomial::new(60, 0.5).unwrap().pmf(30), 0.10257817300856951, 1e-15);
+	}
+
+	#[test] fn test_geometric() {
+		let d = Geometric::new(0.25).unwrap();
+		assert_eq!(d.pmf(0), 0.0);
+		assert_close(d.pmf(3), 0.140625, 1e-15);
+		assert_close(d.cdf(3), 0.578125, 1e-15);
+		assert_eq!(d.quantile(0.578125), Some(3));
+		assert_eq!(d.quantile(0.5), Some(3));
+		assert_eq!((d.mean(), d.variance()), (4.0, 12.0));
+		assert!(Geometric::new(0.0).is_none());
+	}
+
+	#[test] fn test_poisson() {
+		let d = Poisson::new(3.0).unwrap();
+		assert_close(d.pmf(2), 0.22404180765538775, 1e-15);
+		assert_close(d.cdf(2), 0.42319008112684353, 1e-15);
+		assert_eq!(d.quantile(0.42), Some(2));
+		assert_eq!(d.quantile(0.43), Some(3));
+		assert_eq!((d.mean(), d.variance()), (3.0, 3.0));
+	}
+
+	#[test] fn test_hypergeometric() {
+		let d = Hypergeometric::new(50, 5, 10).unwrap();
+		assert_close(d.pmf(1), 0.43133719722856767, 1e-15);
+		assert_close(d.cdf(1), 0.7418999792331363, 1e-15);
+		assert_eq!(d.pmf(6), 0.0);
+		assert_close(d.mean(), 1.0, 1e-15);
+		assert_close((0..=5).map(|k| d.pmf(k)).sum::<f64>(), 1.0, 1e-15);
+
+		// at least 8 of the 10 draws must be successes
+		let d = Hypergeometric::new(10, 8, 10).unwrap();
+		assert_eq!(d.support(), (8, Some(8)));
+		assert_eq!(d.pmf(7), 0.0);
+		assert_eq!(d.pmf(8), 1.0);
+		assert!(Hypergeometric::new(10, 11, 2).is_none());
+
+		let d = Hypergeometric::new(1000, 500, 150).unwrap();
+		assert_close(d.pmf(100), 3.5180484002850514e-6, 1e-18);
+
+		let d = Hypergeometric::new(1, 1, 1).unwrap();
+		assert_eq!((d.pmf(1), d.variance()), (1.0, 0.0));
+	}
+// endregion:
+
+// region: test_continuous
+	#[test] fn test_uniform() {
+		let d = Uniform::new(2.0, 6.0).unwrap();
+		assert_eq!(d.pdf(3.0), 0.25);
+		assert_eq!(d.pdf(7.0), 0.0);
+		assert_eq!(d.cdf(3.0), 0.25);
+		assert_eq!(d.quantile(0.5), Some(4.0));
+		assert_eq!((d.mean(), d.variance()), (4.0, 16.0 / 12.0));
+		assert!(Uniform::new(1.0, 1.0).is_none());
+	}
+
+	#[test] fn test_normal() {
+		let d = Normal::standard();
+		assert_close(d.pdf(0.0), 0.3989422804014327, 1e-15);
+		assert_close(d.cdf(1.96), 0.9750021048517795, 1e-15);
+		assert_close(d.cdf(-8.0), 6.22096057427174e-16, 1e-28);
+		assert_close(d.quantile(0.975).unwrap(), 1.959963984540054, 1e-12);
+		assert_close(d.quantile(1e-10).unwrap(), -6.361340902404056, 1e-9);
+		assert_eq!(d.quantile(1.0), Some(f64::INFINITY));
+
+		let d = Normal::new(10.0, 2.0).unwrap();
+		assert_close(d.cdf(12.0), 0.8413447460685429, 1e-15);
+		assert_close(d.quantile(0.8413447460685429).unwrap(), 12.0, 1e-10);
+		assert_eq!(d.variance(), 4.0);
+		assert!(Normal::new(0.0, 0.0).is_none());
+	}
+
+	#[test] fn test_exponential() {
+		let d = Exponential::new(2.0).unwrap();
+		assert_close(d.cdf(1.0), 1.0 - (-2f64).exp(), 1e-15);
+		assert_close(d.quantile(0.5).unwrap(), 2f64.ln() / 2.0, 1e-15);
+		assert_eq!(d.pdf(-1.0), 0.0);
+		assert_eq!((d.mean(), d.variance()), (0.5, 0.25));
+	}
+
+	#[test] fn test_gamma() {
+		let d = Gamma::new(2.0, 2.0).unwrap();
+		assert_close(d.cdf(2.0), 0.26424111765711533, 1e-15);
+		assert_close(d.pdf(2.0), (-1f64).exp() / 2.0, 1e-15);
+		assert_eq!((d.mean(), d.variance()), (4.0, 8.0));
+
+		let chi = Gamma::chi_square(4.0).unwrap();
+		assert_close(chi.quantile(0.95).unwrap(), 9.487729036781154, 1e-9);
+		assert_close(chi.cdf(chi.quantile(0.3).unwrap()), 0.3, 1e-12);
+		assert!(Gamma::new(-1.0, 1.0).is_none());
+	}
+
+	#[test] fn test_student_t() {
+		let d = StudentT::new(10.0).unwrap();
+		assert_close(d.cdf(2.0), 0.9633059826146298, 1e-14);
+		assert_close(d.cdf(0.0), 0.5, 1e-15);
+		assert_close(d.quantile(0.975).unwrap(), 2.228138851986274, 1e-10);
+		assert_eq!((d.mean(), d.variance()), (0.0, 1.25));
+
+		let d = StudentT::new(3.0).unwrap();
+		assert_close(d.cdf(-4.0), 0.01400422800507308, 1e-15);
+		assert_close(StudentT::new(5.0).unwrap().quantile(0.001).unwrap(), -5.89342953135601, 1e-9);
+		assert_close(StudentT::new(4.0).unwrap().pdf(1.5), 0.12288, 1e-15);
+		// one degree of freedom is the Cauchy distribution
+		assert_close(StudentT::new(1.0).unwrap().cdf(1.0), 0.75, 1e-15);
+		assert!(StudentT::new(1.0).unwrap().mean().is_nan());
+		assert!(StudentT::new(0.0).is_none());
+	}
+// endregion:
+
+// region: test_sampling
+	fn sample_mean(samples: &[f64]) -> f64 {
+		samples.iter().sum::<f64>() / samples.len() as f64
+	}
+
+	#[test] fn test_discrete_sampling() {
+		let mut rng = StdRng::seed_from_u64(7);
+		let d = Binomial::new(20, 0.3).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng) as f64).collect();
+		assert_close(sample_mean(&samples), d.mean(), 0.05);
+		assert!(samples.iter().all(|x| *x <= 20.0));
+
+		let d = Poisson::new(4.0).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng) as f64).collect();
+		assert_close(sample_mean(&samples), 4.0, 0.05);
+
+		let d = Bernoulli::new(0.25).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng) as f64).collect();
+		assert_close(sample_mean(&samples), 0.25, 0.01);
+
+		let d = Hypergeometric::new(50, 20, 10).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng) as f64).collect();
+		assert_close(sample_mean(&samples), 4.0, 0.05);
+
+		let d = Geometric::new(0.2).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng) as f64).collect();
+		assert_close(sample_mean(&samples), 5.0, 0.15);
+	}
+
+	#[test] fn test_continuous_sampling() {
+		let mut rng = StdRng::seed_from_u64(11);
+		let d = Normal::new(5.0, 2.0).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng)).collect();
+		let mean = sample_mean(&samples);
+		let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+		assert_close(mean, 5.0, 0.05);
+		assert_close(variance, 4.0, 0.15);
+
+		let d = Gamma::new(0.5, 3.0).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng)).collect();
+		assert_close(sample_mean(&samples), 1.5, 0.05);
+		assert!(samples.iter().all(|x| *x >= 0.0));
+
+		let d = Exponential::new(0.5).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng)).collect();
+		assert_close(sample_mean(&samples), 2.0, 0.05);
+
+		let d = StudentT::new(5.0).unwrap();
+		let samples: Vec<f64> = (0..20000).map(|_| d.sample(&mut rng)).collect();
+		assert_close(sample_mean(&samples), 0.0, 0.05);
+	}
+// endregion:
+}
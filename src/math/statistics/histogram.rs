@@ -0,0 +1,318 @@
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+use crate::math::datatypes::rkgtab::RkgTabN;
+use crate::math::statistics::descriptive::{max, min, quantile, standard_deviation};
+
+// The table functions read a collumn of a 2d table, or the values of a 1d
+// table when col is 0.  They return None when there is no such collumn, it
+// is empty, or the options do not make sense, like a bin width of zero.
+
+// MARK: Bins
+
+/// The most bins `BinRule::Count` or `BinRule::Width` can ask for
+pub const MAX_BINS: usize = 1 << 20;
+
+/// How the edges of the bins of a histogram are chosen
+#[derive(Clone, Debug, PartialEq)]
+pub enum BinRule {
+	/// this many bins of equal width from the smallest to the largest value,
+	/// at most MAX_BINS
+	Count(usize),
+	/// bins of this width starting at the smallest value, at most MAX_BINS of them
+	Width(f64),
+	/// these edges, which must be increasing, values outside them are dropped
+	Edges(Vec<f64>),
+	/// log2(n) + 1 bins, best for data close to normal
+	Sturges,
+	/// bins 3.49 standard deviations / n^(1/3) wide, at most one per value
+	Scott,
+	/// bins 2 interquartile ranges / n^(1/3) wide, at most one per value
+	///
+	/// The width is not thrown off by outliers, but the range still is, so
+	/// a far outlier is what usually hits the limit.
+	FreedmanDiaconis,
+}
+
+/// What each bin of a histogram holds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistogramScale {
+	/// the number of values in the bin
+	Count,
+	/// the fraction of values in the bin divided by its width, so the
+	/// histogram has an area of one
+	Density,
+}
+
+/// returns the edges of the bins for data, one more than the number of bins
+/// every bin includes its lower edge, the last also includes its upper edge
+/// None if a count or width asks for more than MAX_BINS bins
+pub fn bin_edges(data: &[f64], rule: &BinRule) -> Option<Vec<f64>> {
+	if let BinRule::Edges(edges) = rule {
+		let increasing = edges.windows(2).all(|pair| pair[0] < pair[1]);
+		return (edges.len() >= 2 && increasing).then(|| edges.clone())
+	}
+
+	let (low, high) = (min(data)?, max(data)?);
+	if !low.is_finite() || !high.is_finite() { return None }
+	// a single value gets one bin of width one around it
+	if low == high { return Some(vec![low - 0.5, high + 0.5]) }
+
+	let n = data.len() as f64;
+	let range = high - low;
+	let sturges = n.log2().ceil() as usize + 1;
+	// the rules that pick a width from the data get no more bins than values
+	let most = data.len().max(sturges);
+	let count = match rule {
+		BinRule::Count(0) => return None,
+		BinRule::Count(count) if *count > MAX_BINS => return None,
+		BinRule::Count(count) => *count,
+		BinRule::Width(width) if width.is_nan() || *width <= 0.0 => return None,
+		BinRule::Width(width) => {
+			let count = (range / width).ceil().max(1.0);
+			if count > MAX_BINS as f64 { return None }
+			return Some((0..=count as usize).map(|i| low + i as f64 * width).collect())
+		}
+		BinRule::Sturges => sturges,
+		BinRule::Scott => count_for_width(range, 3.49 * standard_deviation(data).unwrap_or(0.0) / n.cbrt(), most),
+		BinRule::FreedmanDiaconis => {
+			let iqr = quantile(data, 0.75)? - quantile(data, 0.25)?;
+			count_for_width(range, 2.0 * iqr / n.cbrt(), most)
+		}
+		BinRule::Edges(_) => unreachable!(),
+	};
+
+	let width = range / count as f64;
+	let mut edges: Vec<f64> = (0..count).map(|i| low + i as f64 * width).collect();
+	edges.push(high);
+	Some(edges)
+}
+
+// the number of equal bins closest to the width, one if the width is zero,
+// and no more than most
+fn count_for_width(range: f64, width: f64, most: usize) -> usize {
+	if width > 0.0 { ((range / width).ceil().max(1.0) as usize).min(most) } else { 1 }
+}
+
+/// returns the number of values of data in each bin
+/// the edges must be strictly increasing, fewer than two edges have no bins
+pub fn bin_counts(data: &[f64], edges: &[f64]) -> Vec<usize> {
+	if edges.len() < 2 { return Vec::new() }
+
+	let mut counts = vec![0; edges.len() - 1];
+	let last = edges[edges.len() - 1];
+
+	for value in data {
+		if *value < edges[0] || *value > last || value.is_nan() { continue }
+		let bin = edges.partition_point(|e| e <= value).min(counts.len());
+		counts[bin - 1] += 1;
+	}
+	counts
+}
+
+/// returns a histogram of a collumn with a row for each bin and the
+/// collumns lower, upper, and count or density
+pub fn histogram<T>(table: &RkgTabN<T>, col: usize, rule: &BinRule, scale: HistogramScale) -> Option<RkgTabN<f64>>
+where T: Clone + Copy + Display + Into<f64> {
+	let data = collumn(table, col)?;
+	let edges = bin_edges(&data, rule)?;
+	let counts = bin_counts(&data, &edges);
+	let total: usize = counts.iter().sum();
+
+	let mut flat = Vec::with_capacity(counts.len() * 3);
+	for (i, count) in counts.iter().enumerate() {
+		let (lower, upper) = (edges[i], edges[i + 1]);
+		let value = match scale {
+			HistogramScale::Count => *count as f64,
+			HistogramScale::Density => *count as f64 / (total as f64 * (upper - lower)),
+		};
+		flat.extend([lower, upper, value]);
+	}
+
+	let value_label = match scale {
+		HistogramScale::Count => "count",
+		HistogramScale::Density => "density",
+	};
+	Some(labeled(&flat, &["lower", "upper", value_label]))
+}
+
+// MARK: Kernel density
+
+/// Silverman's rule of thumb for the bandwidth of a gaussian kernel
+/// None if data has less than two values or no spread
+pub fn silverman_bandwidth(data: &[f64]) -> Option<f64> {
+	let sd = standard_deviation(data)?;
+	let iqr = quantile(data, 0.75)? - quantile(data, 0.25)?;
+	// the interquartile range is zero when most values are the same
+	let spread = if iqr > 0.0 { sd.min(iqr / 1.34) } else { sd };
+	let bandwidth = 0.9 * spread * (data.len() as f64).powf(-0.2);
+	(bandwidth > 0.0).then_some(bandwidth)
+}
+
+/// the gaussian kernel density estimate of data at x
+/// None if data is empty or bandwidth is not positive
+pub fn gaussian_kde_at(data: &[f64], bandwidth: f64, x: f64) -> Option<f64> {
+	if data.is_empty() || bandwidth.is_nan() || bandwidth <= 0.0 { return None }
+
+	let sum: f64 = data.iter().map(|v| (-0.5 * ((x - v) / bandwidth).powi(2)).exp()).sum();
+	Some(sum / (data.len() as f64 * bandwidth * (2.0 * PI).sqrt()))
+}
+
+/// returns the gaussian kernel density estimate of a collumn at points evenly
+/// spaced from three bandwidths below the smallest value to three above the
+/// largest, with the collumns x and density
+/// the bandwidth is found with Silverman's rule when it is None
+/// None if there are less than two points
+pub fn gaussian_kde<T>(table: &RkgTabN<T>, col: usize, bandwidth: Option<f64>, points: usize) -> Option<RkgTabN<f64>>
+where T: Clone + Copy + Display + Into<f64> {
+	if points < 2 { return None }
+
+	let data = collumn(table, col)?;
+	let bandwidth = match bandwidth {
+		Some(bandwidth) => bandwidth,
+		None => silverman_bandwidth(&data)?,
+	};
+
+	let low = min(&data)? - 3.0 * bandwidth;
+	let step = (max(&data)? + 3.0 * bandwidth - low) / (points - 1) as f64;
+
+	let mut flat = Vec::with_capacity(points * 2);
+	for i in 0..points {
+		let x = low + i as f64 * step;
+		flat.extend([x, gaussian_kde_at(&data, bandwidth, x)?]);
+	}
+	Some(labeled(&flat, &["x", "density"]))
+}
+
+// the values of a collumn of a 2d table, or of a 1d table when col is 0
+fn collumn<T: Clone + Copy + Display + Into<f64>>(table: &RkgTabN<T>, col: usize) -> Option<Vec<f64>> {
+	let shape = table.shape();
+	let values: Vec<f64> = match shape.len() {
+		1 if col == 0 => (0..shape[0]).map(|i| table.get(&[i]).into()).collect(),
+		2 if col < shape[1] => (0..shape[0]).map(|i| table.get(&[i, col]).into()).collect(),
+		_ => return None,
+	};
+	(!values.is_empty()).then_some(values)
+}
+
+// a table with a row for every labels.len() values of flat
+fn labeled(flat: &[f64], labels: &[&str]) -> RkgTabN<f64> {
+	let mut table = RkgTabN::from_flat(flat, &[flat.len() / labels.len(), labels.len()]);
+	let labels: Vec<String> = labels.iter().map(|l| l.to_string()).collect();
+	table.set_labels_from_flat(&labels, &[1]);
+	table
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+		assert!((actual - expected).abs() <= tolerance, "expected {} but got {}", expected, actual);
+	}
+
+	const DATA: [f64; 10] = [1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 5.0, 9.0];
+
+	// region: test_bins
+	#[test] fn test_fixed_bins() {
+		assert_eq!(bin_edges(&DATA, &BinRule::Count(4)), Some(vec![1.0, 3.0, 5.0, 7.0, 9.0]));
+		assert_eq!(bin_edges(&DATA, &BinRule::Width(3.0)), Some(vec![1.0, 4.0, 7.0, 10.0]));
+		assert_eq!(bin_edges(&DATA, &BinRule::Edges(vec![0.0, 5.0])), Some(vec![0.0, 5.0]));
+		assert_eq!(bin_edges(&[2.0, 2.0], &BinRule::Sturges), Some(vec![1.5, 2.5]));
+
+		assert_eq!(bin_edges(&DATA, &BinRule::Count(0)), None);
+		assert_eq!(bin_edges(&DATA, &BinRule::Width(-1.0)), None);
+		assert_eq!(bin_edges(&DATA, &BinRule::Edges(vec![1.0, 1.0])), None);
+		assert_eq!(bin_edges(&[], &BinRule::Sturges), None);
+	}
+
+	#[test] fn test_automatic_bins() {
+		// ceil(log2 10) + 1
+		assert_eq!(bin_edges(&DATA, &BinRule::Sturges).unwrap().len(), 6);
+
+		// width 3.49 * 2.2211 / 10^(1/3) = 3.598 gives 3 bins over a range of 8
+		assert_eq!(bin_edges(&DATA, &BinRule::Scott).unwrap().len(), 4);
+
+		// interquartile range of 1.75 gives a width of 1.625 so 5 bins
+		assert_eq!(bin_edges(&DATA, &BinRule::FreedmanDiaconis).unwrap().len(), 6);
+	}
+
+	#[test] fn test_too_many_bins() {
+		assert_eq!(bin_edges(&[0.0, 1.0], &BinRule::Width(1e-12)), None);
+		assert_eq!(bin_edges(&[0.0, 1.0], &BinRule::Count(MAX_BINS + 1)), None);
+		assert_eq!(bin_edges(&[0.0, 1.0], &BinRule::Width(1.0 / MAX_BINS as f64)).unwrap().len(), MAX_BINS + 1);
+
+		// one far outlier would ask for about 10^9 bins of width 0.02
+		let mut data: Vec<f64> = (0..1000).map(|i| i as f64 / 999.0).collect();
+		data.push(1e9);
+		let edges = bin_edges(&data, &BinRule::FreedmanDiaconis).unwrap();
+		assert_eq!(edges.len(), 1002);
+		assert_eq!((edges[0], edges[1001]), (0.0, 1e9));
+	}
+
+	#[test] fn test_counts() {
+		let edges = [1.0, 3.0, 5.0, 7.0, 9.0];
+		assert_eq!(bin_counts(&DATA, &edges), vec![3, 5, 1, 1]);
+		// values outside the edges are dropped
+		assert_eq!(bin_counts(&DATA, &[2.0, 4.0]), vec![7]);
+		assert_eq!(bin_counts(&DATA, &[2.0, 3.0, 3.5]), vec![2, 3]);
+		// a single edge has no bins
+		assert_eq!(bin_counts(&DATA, &[2.0]), Vec::<usize>::new());
+		assert_eq!(bin_counts(&DATA, &[]), Vec::<usize>::new());
+	}
+	// endregion:
+
+	// region: test_histogram
+	#[test] fn test_histogram_table() {
+		let table = RkgTabN::from_flat(&DATA, &[10]);
+		let counts = histogram(&table, 0, &BinRule::Count(4), HistogramScale::Count).unwrap();
+		assert_eq!(counts.shape(), vec![4, 3]);
+		assert_eq!((counts.get(&[1, 0]), counts.get(&[1, 1]), counts.get(&[1, 2])), (3.0, 5.0, 5.0));
+		assert_eq!(counts.get_label_by_axis(1, 2), "count");
+
+		let density = histogram(&table, 0, &BinRule::Count(4), HistogramScale::Density).unwrap();
+		assert_eq!(density.get(&[1, 2]), 0.25);
+		let area: f64 = (0..4).map(|i| density.get(&[i, 2]) * 2.0).sum();
+		assert_close(area, 1.0, 1e-12);
+		assert_eq!(density.get_label_by_axis(1, 2), "density");
+	}
+
+	#[test] fn test_histogram_collumn() {
+		let table = RkgTabN::from_flat(&[1, 10, 2, 20, 3, 30], &[3, 2]);
+		let counts = histogram(&table, 1, &BinRule::Count(2), HistogramScale::Count).unwrap();
+		assert_eq!((counts.get(&[0, 0]), counts.get(&[1, 1])), (10.0, 30.0));
+		assert_eq!((counts.get(&[0, 2]), counts.get(&[1, 2])), (1.0, 2.0));
+		assert!(histogram(&table, 2, &BinRule::Sturges, HistogramScale::Count).is_none());
+	}
+	// endregion:
+
+	// region: test_kde
+	#[test] fn test_kde() {
+		assert_close(gaussian_kde_at(&[0.0], 1.0, 0.0).unwrap(), 1.0 / (2.0 * PI).sqrt(), 1e-15);
+		assert_close(gaussian_kde_at(&[-1.0, 1.0], 2.0, 1.0).unwrap(), 0.16022825123014403, 1e-12);
+		assert!(gaussian_kde_at(&[], 1.0, 0.0).is_none());
+		assert!(gaussian_kde_at(&[1.0], 0.0, 0.0).is_none());
+
+		// 0.9 * min(2.2211, 1.75 / 1.34) * 10^-0.2
+		assert_close(silverman_bandwidth(&DATA).unwrap(), 0.7416103116091823, 1e-12);
+		assert!(silverman_bandwidth(&[3.0, 3.0]).is_none());
+	}
+
+	#[test] fn test_kde_table() {
+		let table = RkgTabN::from_flat(&DATA, &[10]);
+		let kde = gaussian_kde(&table, 0, Some(0.5), 201).unwrap();
+		assert_eq!(kde.shape(), vec![201, 2]);
+		assert_eq!(kde.get(&[0, 0]), -0.5);
+		assert_close(kde.get(&[200, 0]), 10.5, 1e-12);
+		assert_eq!(kde.get_label_by_axis(1, 1), "density");
+
+		// the density integrates to about one over the grid
+		let step = kde.get(&[1, 0]) - kde.get(&[0, 0]);
+		let area: f64 = (0..201).map(|i| kde.get(&[i, 1]) * step).sum();
+		assert_close(area, 1.0, 1e-2);
+
+		assert!(gaussian_kde(&table, 0, None, 50).is_some());
+		assert!(gaussian_kde(&table, 0, None, 1).is_none());
+	}
+	// endregion:
+}
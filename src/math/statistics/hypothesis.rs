@@ -0,0 +1,331 @@
+use std::fmt::Display;
+
+use crate::math::datatypes::rkgtab::RkgTabN;
+use crate::math::statistics::descriptive::{mean, variance};
+use crate::math::statistics::distributions::{Normal, StudentT, TContinuousDistribution, gamma_q};
+
+// Every test returns None when its input can not be tested, like samples too
+// small to estimate a variance or a confidence level outside (0, 1).
+
+// MARK: TestResult
+
+/// The side of the null hypothesis the alternative hypothesis is on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alternative {
+	/// the true value is not the hypothesised one
+	TwoSided,
+	/// the true value is less than the hypothesised one
+	Less,
+	/// the true value is greater than the hypothesised one
+	Greater,
+}
+
+/// The outcome of a hypothesis test
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TestResult {
+	/// the test statistic
+	pub statistic: f64,
+	/// degrees of freedom of the statistic, None for z-tests
+	pub df: Option<f64>,
+	/// probubility of a statistic at least this extreme if the null hypothesis is true
+	pub p_value: f64,
+}
+
+impl TestResult {
+	/// returns true if the null hypothesis is rejected at significance level alpha
+	pub fn is_significant(&self, alpha: f64) -> bool {
+		self.p_value < alpha
+	}
+}
+
+// p-value of a statistic from a distribution that is symmetric about zero
+fn p_value(distribution: &impl TContinuousDistribution, statistic: f64, alternative: Alternative) -> f64 {
+	match alternative {
+		Alternative::Less => distribution.cdf(statistic),
+		Alternative::Greater => distribution.cdf(-statistic),
+		Alternative::TwoSided => (2.0 * distribution.cdf(-statistic.abs())).min(1.0),
+	}
+}
+
+// the t-test of a difference over its standard error, None if the error is
+// zero like for constant samples, since the statistic is then undefined
+fn t_result(difference: f64, standard_error: f64, df: f64, alternative: Alternative) -> Option<TestResult> {
+	if !(standard_error > 0.0 && standard_error.is_finite()) { return None }
+
+	let distribution = StudentT::new(df)?;
+	let statistic = difference / standard_error;
+	Some(TestResult { statistic, df: Some(df), p_value: p_value(&distribution, statistic, alternative) })
+}
+
+fn chi_square_result(statistic: f64, df: f64) -> Option<TestResult> {
+	if df < 1.0 { return None }
+	Some(TestResult { statistic, df: Some(df), p_value: gamma_q(df / 2.0, statistic / 2.0) })
+}
+
+// MARK: t-tests
+
+/// One sample t-test of whether the mean of data is mu
+/// None if data has less than two values or they are all the same
+pub fn t_test_one_sample(data: &[f64], mu: f64, alternative: Alternative) -> Option<TestResult> {
+	let standard_error = (variance(data)? / data.len() as f64).sqrt();
+	t_result(mean(data)? - mu, standard_error, (data.len() - 1) as f64, alternative)
+}
+
+/// Welch's t-test of whether a and b have the same mean, it does not assume
+/// they have the same variance
+/// None if either sample has less than two values or both are constant
+pub fn t_test_two_sample(a: &[f64], b: &[f64], alternative: Alternative) -> Option<TestResult> {
+	let va = variance(a)? / a.len() as f64;
+	let vb = variance(b)? / b.len() as f64;
+
+	// Welch-Satterthwaite approximation of the degrees of freedom
+	let df = (va + vb).powi(2) / (va * va / (a.len() - 1) as f64 + vb * vb / (b.len() - 1) as f64);
+	t_result(mean(a)? - mean(b)?, (va + vb).sqrt(), df, alternative)
+}
+
+/// Student's t-test of whether a and b have the same mean, assuming they
+/// have the same variance
+/// None if the samples have less than three values between them, either is
+/// empty or both are constant
+pub fn t_test_pooled(a: &[f64], b: &[f64], alternative: Alternative) -> Option<TestResult> {
+	if a.is_empty() || b.is_empty() || a.len() + b.len() < 3 { return None }
+
+	let df = (a.len() + b.len() - 2) as f64;
+	let sum_of_squares = |data: &[f64]| variance(data).map_or(0.0, |v| v * (data.len() - 1) as f64);
+	let pooled = (sum_of_squares(a) + sum_of_squares(b)) / df;
+
+	let standard_error = (pooled * (1.0 / a.len() as f64 + 1.0 / b.len() as f64)).sqrt();
+	t_result(mean(a)? - mean(b)?, standard_error, df, alternative)
+}
+
+/// Paired t-test of whether the mean difference between a and b is zero
+/// None if the lengths differ, there are less than two pairs or every pair
+/// has the same difference
+pub fn t_test_paired(a: &[f64], b: &[f64], alternative: Alternative) -> Option<TestResult> {
+	if a.len() != b.len() { return None }
+	let differences: Vec<f64> = a.iter().zip(b).map(|(x, y)| x - y).collect();
+	t_test_one_sample(&differences, 0.0, alternative)
+}
+
+// MARK: Chi-square tests
+
+/// Pearson's chi-square test of whether observed counts follow the expected
+/// proportions, expected is scaled to the same total as observed
+/// None if the lengths differ, there are less than two categories or an
+/// expected value is not positive
+pub fn chi_square_goodness_of_fit(observed: &[f64], expected: &[f64]) -> Option<TestResult> {
+	if observed.len() != expected.len() || observed.len() < 2 { return None }
+	if expected.iter().any(|e| e.is_nan() || *e <= 0.0) { return None }
+
+	let scale = observed.iter().sum::<f64>() / expected.iter().sum::<f64>();
+	let statistic = observed.iter()
+		.zip(expected)
+		.map(|(o, e)| (o - e * scale).powi(2) / (e * scale))
+		.sum();
+
+	chi_square_result(statistic, (observed.len() - 1) as f64)
+}
+
+/// Pearson's chi-square test of whether the rows and collumns of a 2d
+/// contingency table are independent
+/// None if the table is not 2d, is smaller than 2 by 2, or has a row or
+/// collumn that adds to zero
+pub fn chi_square_independence<T: Clone + Copy + Display + Into<f64>>(table: &RkgTabN<T>) -> Option<TestResult> {
+	let shape = table.shape();
+	if shape.len() != 2 || shape[0] < 2 || shape[1] < 2 { return None }
+
+	let cell = |i: usize, j: usize| -> f64 { table.get(&[i, j]).into() };
+	let rows: Vec<f64> = (0..shape[0]).map(|i| (0..shape[1]).map(|j| cell(i, j)).sum()).collect();
+	let cols: Vec<f64> = (0..shape[1]).map(|j| (0..shape[0]).map(|i| cell(i, j)).sum()).collect();
+	if rows.iter().chain(&cols).any(|total| total.is_nan() || *total <= 0.0) { return None }
+
+	let total: f64 = rows.iter().sum();
+	let mut statistic = 0.0;
+	for (i, row) in rows.iter().enumerate() {
+		for (j, col) in cols.iter().enumerate() {
+			let expected = row * col / total;
+			statistic += (cell(i, j) - expected).powi(2) / expected;
+		}
+	}
+
+	chi_square_result(statistic, ((shape[0] - 1) * (shape[1] - 1)) as f64)
+}
+
+// MARK: z-tests
+
+/// Two proportion z-test of whether successes_a / n_a and successes_b / n_b
+/// come from the same proportion, using the pooled proportion
+/// None if a sample is empty, has more successes than trials, or every
+/// trial in both samples has the same outcome
+pub fn two_proportion_z_test(
+	successes_a: u64, n_a: u64,
+	successes_b: u64, n_b: u64,
+	alternative: Alternative
+) -> Option<TestResult> {
+	if n_a == 0 || n_b == 0 || successes_a > n_a || successes_b > n_b { return None }
+
+	let (n_a, n_b) = (n_a as f64, n_b as f64);
+	let pooled = (successes_a + successes_b) as f64 / (n_a + n_b);
+	let standard_error = (pooled * (1.0 - pooled) * (1.0 / n_a + 1.0 / n_b)).sqrt();
+	if standard_error == 0.0 { return None }
+
+	let statistic = (successes_a as f64 / n_a - successes_b as f64 / n_b) / standard_error;
+	Some(TestResult { statistic, df: None, p_value: p_value(&Normal::standard(), statistic, alternative) })
+}
+
+// MARK: Confidence intervals
+
+/// The t interval (lower, upper) that holds the mean of the population data
+/// was drawn from with the given confidence
+/// None if data has less than two values or confidence is not in (0, 1)
+pub fn mean_confidence_interval(data: &[f64], confidence: f64) -> Option<(f64, f64)> {
+	if !(confidence > 0.0 && confidence < 1.0) { return None }
+
+	let distribution = StudentT::new((data.len().max(1) - 1) as f64)?;
+	let critical = distribution.quantile(0.5 + confidence / 2.0)?;
+	let half_width = critical * (variance(data)? / data.len() as f64).sqrt();
+	let mean = mean(data)?;
+	Some((mean - half_width, mean + half_width))
+}
+
+/// The Wilson score interval (lower, upper) for a proportion from successes
+/// out of n trials, it stays inside [0, 1] even with few successes
+/// None if n is zero, successes is more than n or confidence is not in (0, 1)
+pub fn proportion_confidence_interval(successes: u64, n: u64, confidence: f64) -> Option<(f64, f64)> {
+	if n == 0 || successes > n || !(confidence > 0.0 && confidence < 1.0) { return None }
+
+	let z = Normal::standard().quantile(0.5 + confidence / 2.0)?;
+	let (p, n) = (successes as f64 / n as f64, n as f64);
+	let denominator = 1.0 + z * z / n;
+	let center = (p + z * z / (2.0 * n)) / denominator;
+	let half_width = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+	Some(((center - half_width).max(0.0), (center + half_width).min(1.0)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+		assert!((actual - expected).abs() <= tolerance, "expected {} but got {}", expected, actual);
+	}
+
+	const A: [f64; 8] = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 5.7, 5.5];
+	const B: [f64; 7] = [4.8, 4.6, 5.2, 4.9, 5.0, 4.7, 5.1];
+
+	// region: test_t_tests
+	#[test] fn test_one_sample() {
+		let result = t_test_one_sample(&A, 5.0, Alternative::TwoSided).unwrap();
+		assert_close(result.statistic, 3.745352851760395, 1e-12);
+		assert_eq!(result.df, Some(7.0));
+		assert_close(result.p_value, 0.007211953219839423, 1e-12);
+		assert!(result.is_significant(0.05));
+
+		let greater = t_test_one_sample(&A, 5.0, Alternative::Greater).unwrap();
+		assert_close(greater.p_value, 0.003605976609919712, 1e-12);
+		let less = t_test_one_sample(&A, 5.0, Alternative::Less).unwrap();
+		assert_close(less.p_value, 1.0 - greater.p_value, 1e-12);
+		assert!(t_test_one_sample(&[1.0], 0.0, Alternative::TwoSided).is_none());
+	}
+
+	#[test] fn test_two_sample() {
+		let welch = t_test_two_sample(&A, &B, Alternative::TwoSided).unwrap();
+		assert_close(welch.statistic, 3.823600143662985, 1e-12);
+		assert_close(welch.df.unwrap(), 11.513069807122538, 1e-10);
+		assert_close(welch.p_value, 0.002606234049370861, 1e-12);
+
+		let pooled = t_test_pooled(&A, &B, Alternative::TwoSided).unwrap();
+		assert_close(pooled.statistic, 3.692293074111797, 1e-12);
+		assert_eq!(pooled.df, Some(13.0));
+		assert_close(pooled.p_value, 0.002709571715648179, 1e-12);
+		assert!(t_test_pooled(&A, &[], Alternative::TwoSided).is_none());
+	}
+
+	#[test] fn test_paired() {
+		let c = [5.0, 4.7, 5.9, 5.5, 5.6, 5.4, 5.9, 5.2];
+		let result = t_test_paired(&A, &c, Alternative::TwoSided).unwrap();
+		assert_close(result.statistic, 0.9563820714895625, 1e-12);
+		assert_close(result.p_value, 0.3707343902707681, 1e-12);
+		assert!(!result.is_significant(0.05));
+		assert!(t_test_paired(&A, &B, Alternative::TwoSided).is_none());
+	}
+
+	#[test] fn test_constant_samples() {
+		// no spread means no standard error, whether or not the mean matches
+		let fives = [5.0, 5.0, 5.0];
+		assert!(t_test_one_sample(&fives, 5.0, Alternative::TwoSided).is_none());
+		assert!(t_test_one_sample(&fives, 4.0, Alternative::Greater).is_none());
+		assert!(t_test_two_sample(&fives, &[4.0, 4.0], Alternative::TwoSided).is_none());
+		assert!(t_test_pooled(&fives, &fives, Alternative::TwoSided).is_none());
+		assert!(t_test_pooled(&fives, &[4.0], Alternative::TwoSided).is_none());
+		assert!(t_test_paired(&fives, &fives, Alternative::TwoSided).is_none());
+		assert!(t_test_paired(&A, &A.map(|x| x - 1.0), Alternative::TwoSided).is_none());
+
+		// one constant sample is fine when the other has spread
+		assert!(t_test_pooled(&fives, &B, Alternative::TwoSided).is_some());
+	}
+	// endregion:
+
+	// region: test_chi_square
+	#[test] fn test_goodness_of_fit() {
+		let observed = [22.0, 17.0, 20.0, 26.0, 22.0, 13.0];
+		let result = chi_square_goodness_of_fit(&observed, &[1.0; 6]).unwrap();
+		assert_close(result.statistic, 5.1, 1e-12);
+		assert_eq!(result.df, Some(5.0));
+		assert_close(result.p_value, 0.4037984571042082, 1e-12);
+		assert!(chi_square_goodness_of_fit(&observed, &[1.0; 5]).is_none());
+		assert!(chi_square_goodness_of_fit(&[1.0, 2.0], &[1.0, 0.0]).is_none());
+	}
+
+	#[test] fn test_independence() {
+		let table = RkgTabN::from_flat(&[20, 30, 30, 20], &[2, 2]);
+		let result = chi_square_independence(&table).unwrap();
+		assert_close(result.statistic, 4.0, 1e-12);
+		assert_eq!(result.df, Some(1.0));
+		assert_close(result.p_value, 0.04550026389635841, 1e-12);
+
+		let table = RkgTabN::from_flat(&[10, 20, 30, 15, 25, 10], &[2, 3]);
+		let result = chi_square_independence(&table).unwrap();
+		assert_close(result.statistic, 10.735185185185185, 1e-12);
+		assert_eq!(result.df, Some(2.0));
+		assert_close(result.p_value, 0.004665349198069897, 1e-12);
+
+		assert!(chi_square_independence(&RkgTabN::from_flat(&[1, 2, 3], &[3])).is_none());
+		assert!(chi_square_independence(&RkgTabN::from_flat(&[1, 0, 2, 0], &[2, 2])).is_none());
+	}
+	// endregion:
+
+	// region: test_z_tests
+	#[test] fn test_two_proportion() {
+		let result = two_proportion_z_test(45, 100, 30, 100, Alternative::TwoSided).unwrap();
+		assert_close(result.statistic, 2.1908902300206645, 1e-12);
+		assert_eq!(result.df, None);
+		assert_close(result.p_value, 0.02845973691631058, 1e-12);
+
+		let greater = two_proportion_z_test(45, 100, 30, 100, Alternative::Greater).unwrap();
+		assert_close(greater.p_value, 0.01422986845815529, 1e-12);
+		assert!(two_proportion_z_test(5, 4, 1, 4, Alternative::TwoSided).is_none());
+		assert!(two_proportion_z_test(0, 10, 0, 10, Alternative::TwoSided).is_none());
+	}
+	// endregion:
+
+	// region: test_confidence_intervals
+	#[test] fn test_mean_interval() {
+		let (lower, upper) = mean_confidence_interval(&A, 0.95).unwrap();
+		assert_close(lower, 5.179717430966574, 1e-10);
+		assert_close(upper, 5.795282569033426, 1e-10);
+		assert!(mean_confidence_interval(&A, 1.0).is_none());
+		assert!(mean_confidence_interval(&[], 0.95).is_none());
+	}
+
+	#[test] fn test_proportion_interval() {
+		let (lower, upper) = proportion_confidence_interval(45, 100, 0.95).unwrap();
+		assert_close(lower, 0.3561453797951198, 1e-10);
+		assert_close(upper, 0.5475539700255787, 1e-10);
+
+		let (lower, _) = proportion_confidence_interval(0, 20, 0.95).unwrap();
+		assert_close(lower, 0.0, 1e-15);
+		assert!(proportion_confidence_interval(3, 2, 0.95).is_none());
+	}
+	// endregion:
+}
@@ -4,6 +4,30 @@ pub mod combinatorics;
 /// Contains functions and impl for probubility trait
 pub mod probubility;
 
+/// Contains discrete and continuous probubility distributions
+pub mod distributions;
+
+/// Contains random variables over the elements of a sample space
+pub mod random_variable;
+
+/// Contains descriptive statistics over slices and along the axes of tables
+pub mod descriptive;
+
+/// Contains hypothesis tests and confidence intervals
+pub mod hypothesis;
+
+/// Contains least squares regression on the collumns of tables
+pub mod regression;
+
+/// Contains correlation and covariance between the collumns of tables
+pub mod correlation;
+
+/// Contains histograms and kernel density estimates of table collumns
+pub mod histogram;
+
+/// Contains seedable random sampling from sets and tables
+pub mod sampling;
+
 /// Prints the description of the Statistics modual.
 pub fn description() {
 	println!("This is the statistics modual");
@@ -1,6 +1,38 @@
-use crate::math::datatypes::set::{TSet, TSetElement, VecSet};
+use std::fmt;
+use std::hash::Hash;
+
+use crate::math::datatypes::set::{ArraySet, HashedSet, MultiSet, TSet, TSetElement, TWeight, TreeSet, VecSet, WeightedSet};
+
+// MARK: ProbubilityError
+
+/// Error returned when a probubility between two sets can not be found
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProbubilityError {
+	/// the sets are not both subsets of the same sample space
+	DifferentSampleSpace,
+	/// the event being conditioned on has probubility zero
+	ZeroProbubility,
+}
+
+impl fmt::Display for ProbubilityError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::DifferentSampleSpace => write!(f, "sets are not subsets of the same sample space"),
+			Self::ZeroProbubility => write!(f, "conditioned on an event with probubility zero"),
+		}
+	}
+}
+
+impl std::error::Error for ProbubilityError {}
+
+// MARK: TProbubility
 
 /// Implments functions for calculating probubility for a object
+///
+/// The methods between two sets need both to be subsets of the same sample
+/// space, otherwise they return `ProbubilityError::DifferentSampleSpace`.
+/// They are found from the `probubility` of the combined event, so weighted
+/// sets sum their masses instead of counting elements.
 pub trait TProbubility {
 	/// Retruns true if object is the entire sample space
 	fn is_sample_space(&self) -> bool;
@@ -8,9 +40,69 @@ pub trait TProbubility {
 	/// Returns 0 to 1 the P value of the set
 	/// returns 1 if this is the sample space
 	fn probubility(&self) -> f64;
+
+	/// P(A and B), the probubility that both events happen
+	fn probubility_and<'a, T>(&self, other: &Self) -> Result<f64, ProbubilityError>
+	where Self: TSet<'a, T> + Sized {
+		check_sample_space(self, other)?;
+		Ok(Self::intersection(self, other).probubility())
+	}
+
+	/// P(A or B), the probubility that at least one of the events happens
+	fn probubility_or<'a, T>(&self, other: &Self) -> Result<f64, ProbubilityError>
+	where Self: TSet<'a, T> + Sized {
+		check_sample_space(self, other)?;
+		Ok(Self::union(self, other).probubility())
+	}
+
+	/// P(A | B), the probubility of this event given that other happened
+	/// returns an error if P(B) is zero
+	fn conditional_probubility<'a, T>(&self, given: &Self) -> Result<f64, ProbubilityError>
+	where Self: TSet<'a, T> + Sized {
+		check_sample_space(self, given)?;
+		let p_given = given.probubility();
+		if p_given == 0.0 { return Err(ProbubilityError::ZeroProbubility) }
+
+		Ok(Self::intersection(self, given).probubility() / p_given)
+	}
+
+	/// Returns true if P(A and B) = P(A) P(B), up to floating point rounding
+	fn is_independent<'a, T>(&self, other: &Self) -> Result<bool, ProbubilityError>
+	where Self: TSet<'a, T> + Sized {
+		let both = self.probubility_and(other)?;
+		let product = self.probubility() * other.probubility();
+
+		Ok((both - product).abs() <= 1e-12 * product.max(1e-300))
+	}
+
+	/// P(A | B) found with Bayes' theorem from P(B | A), P(A) and P(B)
+	/// returns an error if P(B) is zero
+	fn bayes<'a, T>(&self, given: &Self) -> Result<f64, ProbubilityError>
+	where Self: TSet<'a, T> + Sized {
+		check_sample_space(self, given)?;
+		let p_given = given.probubility();
+		if p_given == 0.0 { return Err(ProbubilityError::ZeroProbubility) }
+		let p_self = self.probubility();
+		if p_self == 0.0 { return Ok(0.0) }
+
+		let likelihood = given.conditional_probubility(self)?;
+		bayes(likelihood, p_self, p_given)
+	}
+}
+
+/// Bayes' theorem, P(A | B) = P(B | A) P(A) / P(B)
+/// returns an error if P(B) is zero
+pub fn bayes(p_b_given_a: f64, p_a: f64, p_b: f64) -> Result<f64, ProbubilityError> {
+	if p_b == 0.0 { return Err(ProbubilityError::ZeroProbubility) }
+
+	Ok(p_b_given_a * p_a / p_b)
+}
+
+fn check_sample_space<'a, T, S: TSet<'a, T>>(a: &S, b: &S) -> Result<(), ProbubilityError> {
+	if a.shares_parent(b) { Ok(()) } else { Err(ProbubilityError::DifferentSampleSpace) }
 }
 
-impl<'a, T: TSetElement> TProbubility for VecSet<'a, T>{
+impl<T: TSetElement> TProbubility for VecSet<T>{
 	fn is_sample_space(&self) -> bool {
 		!self.is_subset()
 	}
@@ -26,6 +118,77 @@ impl<'a, T: TSetElement> TProbubility for VecSet<'a, T>{
 	}
 }
 
+impl<'a, T: TSetElement> TProbubility for ArraySet<'a, T>{
+	fn is_sample_space(&self) -> bool {
+		!self.is_subset()
+	}
+
+	fn probubility(&self) -> f64 {
+		if self.is_sample_space() { return 1.0 }
+		match self.parent_size() {
+			Some(size) => self.size() as f64 / size as f64,
+			_ => panic!("Set is not sample space but parents size could not be found.")
+		}
+	}
+}
+
+impl<T: Clone + Eq + Hash> TProbubility for HashedSet<T>{
+	fn is_sample_space(&self) -> bool {
+		!self.is_subset()
+	}
+
+	fn probubility(&self) -> f64 {
+		if self.is_sample_space() { return 1.0 }
+		match self.parent_size() {
+			Some(size) => self.size() as f64 / size as f64,
+			_ => panic!("Set is not sample space but parents size could not be found.")
+		}
+	}
+}
+
+impl<T: Clone + Ord> TProbubility for TreeSet<T>{
+	fn is_sample_space(&self) -> bool {
+		!self.is_subset()
+	}
+
+	fn probubility(&self) -> f64 {
+		if self.is_sample_space() { return 1.0 }
+		match self.parent_size() {
+			Some(size) => self.size() as f64 / size as f64,
+			_ => panic!("Set is not sample space but parents size could not be found.")
+		}
+	}
+}
+
+// the mass of the set over the mass of its parent
+impl<T: TSetElement, W: TWeight> TProbubility for WeightedSet<T, W>{
+	fn is_sample_space(&self) -> bool {
+		!self.is_subset()
+	}
+
+	fn probubility(&self) -> f64 {
+		match self.parent_mass() {
+			None => 1.0,
+			Some(total) => self.mass().ratio(&total)
+		}
+	}
+}
+
+// every copy in a multiset is an equally likely outcome
+impl<T: TSetElement> TProbubility for MultiSet<T>{
+	fn is_sample_space(&self) -> bool {
+		!self.is_subset()
+	}
+
+	fn probubility(&self) -> f64 {
+		if self.is_sample_space() { return 1.0 }
+		match self.parent_size() {
+			Some(size) => self.size() as f64 / size as f64,
+			_ => panic!("Set is not sample space but parents size could not be found.")
+		}
+	}
+}
+
 
 
 // MARK: Tests
@@ -33,6 +196,7 @@ impl<'a, T: TSetElement> TProbubility for VecSet<'a, T>{
 mod tests {
 
 	use super::*;
+	use crate::math::datatypes::rational::Rational;
 
 	fn test_is_sample_space(set: impl TProbubility, expected: bool) {
 		assert_eq!(set.is_sample_space(), expected)
@@ -69,4 +233,188 @@ mod tests {
 		),
 		0.25
 	);}
-}
\ No newline at end of file
+
+	#[test] fn test_probubility_2() { test_probubility(
+		VecSet::subset_from_indices(
+			&VecSet::new_from_array(&[1,1,2,2]),
+			&[0]
+		),
+		0.5
+	);}
+
+	#[test] fn test_probubility_3() { test_probubility(
+		MultiSet::subset_from_indices(
+			&MultiSet::new_from_array(&[1,1,2,2]),
+			&[0,1]
+		),
+		0.5
+	);}
+
+	#[test] fn test_probubility_4() { test_probubility(
+		MultiSet::subset_from_indices(
+			&MultiSet::new_from_array(&[1,1,1,2]),
+			&[3]
+		),
+		0.25
+	);}
+
+	#[test] fn test_probubility_5() { test_probubility(
+		ArraySet::subset_from_range(
+			&ArraySet::new_from_array(&[1,2,3,4,5,6,7,8]),
+			2,
+			8
+		),
+		0.75
+	);}
+
+	#[test] fn test_probubility_6() {
+		let faces: Vec<String> = ["1","2","3","4","5","6"].iter().map(|f| f.to_string()).collect();
+		let die = TreeSet::new_from_array(&faces);
+		assert_eq!(die.probubility(), 1.0);
+		assert_eq!(die.subset_from_indices(&[1,3,5]).probubility(), 0.5);
+
+		let die = HashedSet::new_from_array(&faces);
+		assert_eq!(die.subset_from_range(0, 2).probubility(), 2.0 / 6.0);
+	}
+
+	#[test] fn test_probubility_two_dice() {
+		use crate::math::datatypes::set::cartesian_pairs;
+
+		let die = VecSet::new_from_array(&[1,2,3,4,5,6]);
+		let rolls: Vec<(u8, u8)> = cartesian_pairs(&die, &die).collect();
+		let space = VecSet::new_from_array(&rolls);
+
+		let doubles: Vec<usize> = (0..space.size()).filter(|i| space[*i].0 == space[*i].1).collect();
+		assert_eq!(space.subset_from_indices(&doubles).probubility(), 6.0 / 36.0);
+	}
+
+	#[test] fn test_probubility_filter() {
+		let die = VecSet::new_from_array(&[1,2,3,4,5,6]);
+		assert_eq!(die.filter(|x| x % 2 == 0).probubility(), 0.5);
+		assert_eq!(die.filter(|x| *x > 4).probubility(), 2.0 / 6.0);
+	}
+
+// region: test_two_events
+	fn die() -> VecSet<u32> {
+		VecSet::new_from_array(&[1,2,3,4,5,6])
+	}
+
+	#[test] fn test_probubility_and() {
+		let die = die();
+		let even = die.filter(|x| x % 2 == 0);
+		let high = die.filter(|x| *x > 3);
+		assert_eq!(even.probubility_and(&high), Ok(2.0 / 6.0));
+		assert_eq!(even.probubility_or(&high), Ok(4.0 / 6.0));
+	}
+
+	#[test] fn test_conditional_probubility() {
+		let die = die();
+		let even = die.filter(|x| x % 2 == 0);
+		let high = die.filter(|x| *x > 3);
+		assert_eq!(even.conditional_probubility(&high), Ok(2.0 / 3.0));
+		assert_eq!(
+			even.conditional_probubility(&die.filter(|x| *x > 6)),
+			Err(ProbubilityError::ZeroProbubility)
+		);
+	}
+
+	#[test] fn test_is_independent() {
+		let die = die();
+		let even = die.filter(|x| x % 2 == 0);
+		let low = die.filter(|x| *x <= 2);
+		let high = die.filter(|x| *x > 3);
+		assert_eq!(even.is_independent(&low), Ok(true));
+		assert_eq!(even.is_independent(&high), Ok(false));
+	}
+
+	#[test] fn test_bayes() {
+		let die = die();
+		let six = die.filter(|x| *x == 6);
+		let even = die.filter(|x| x % 2 == 0);
+		let p = six.bayes(&even).unwrap();
+		assert!((p - 1.0 / 3.0).abs() < 1e-12);
+		assert!((p - six.conditional_probubility(&even).unwrap()).abs() < 1e-12);
+
+		// a test with 1% prevalence, 99% sensitivity and 5% false positives
+		let p_positive = 0.99 * 0.01 + 0.05 * 0.99;
+		let p = bayes(0.99, 0.01, p_positive).unwrap();
+		assert!((p - 0.1666666666666667).abs() < 1e-12);
+		assert_eq!(bayes(0.5, 0.5, 0.0), Err(ProbubilityError::ZeroProbubility));
+	}
+
+	#[test] fn test_bayes_zero_probubility() {
+		let die = die();
+		let seven = die.filter(|x| *x == 7);
+		let eight = die.filter(|x| *x == 8);
+		// P(B) = 0 is an error even when P(A) = 0 as well
+		assert_eq!(seven.bayes(&eight), Err(ProbubilityError::ZeroProbubility));
+		assert_eq!(die.filter(|x| *x == 6).bayes(&seven), Err(ProbubilityError::ZeroProbubility));
+		assert_eq!(seven.bayes(&die.filter(|x| x % 2 == 0)), Ok(0.0));
+	}
+
+	#[test] fn test_different_sample_space() {
+		let a = die();
+		let b = VecSet::new_from_array(&[1,2,3]);
+		let even = a.filter(|x| x % 2 == 0);
+		let odd = b.filter(|x| x % 2 == 1);
+		assert_eq!(even.probubility_and(&odd), Err(ProbubilityError::DifferentSampleSpace));
+		// the sample space is an event of itself, and independent of every event
+		assert_eq!(even.is_independent(&a), Ok(true));
+		assert_eq!(even.conditional_probubility(&a), Ok(0.5));
+		assert_eq!(a.conditional_probubility(&even), Ok(1.0));
+		assert_eq!(even.is_independent(&b), Err(ProbubilityError::DifferentSampleSpace));
+		assert_eq!(
+			ProbubilityError::DifferentSampleSpace.to_string(),
+			"sets are not subsets of the same sample space"
+		);
+	}
+
+	#[test] fn test_two_events_other_sets() {
+		let array = [1,2,3,4,5,6];
+		let die = ArraySet::new_from_array(&array);
+		let even = die.filter(|x| x % 2 == 0);
+		let high = die.filter(|x| *x > 3);
+		assert_eq!(even.conditional_probubility(&high), Ok(2.0 / 3.0));
+
+		let die = TreeSet::new_from_array(&["1","2","3","4","5","6"]);
+		let even = die.filter(|x| x.parse::<u32>().unwrap() % 2 == 0);
+		let low = die.filter(|x| *x <= "2");
+		assert_eq!(even.is_independent(&low), Ok(true));
+	}
+// endregion:
+
+// region: test_weighted
+	#[test] fn test_weighted_loaded_die() {
+		// a six is three times as likely as any other face
+		let weights: Vec<Rational> = [1,1,1,1,1,3].iter().map(|w| Rational::from(*w)).collect();
+		let die = WeightedSet::new_weighted(&[1,2,3,4,5,6], &weights).unwrap();
+		assert_eq!(die.probubility(), 1.0);
+
+		let six = die.filter(|x| *x == 6);
+		let even = die.filter(|x| x % 2 == 0);
+		assert_eq!(six.probubility(), 3.0 / 8.0);
+		assert_eq!(even.probubility(), 5.0 / 8.0);
+		assert_eq!(six.conditional_probubility(&even), Ok(3.0 / 5.0));
+		assert!((six.bayes(&even).unwrap() - 0.6).abs() < 1e-12);
+		assert_eq!(even.is_independent(&six), Ok(false));
+	}
+
+	#[test] fn test_weighted_categorical() {
+		let colors = WeightedSet::new_weighted(&["red","green","blue"], &[0.5, 0.3, 0.2]).unwrap();
+		let not_blue = colors.filter(|c| *c != "blue");
+		assert!((not_blue.probubility() - 0.8).abs() < 1e-12);
+		assert!((not_blue.complement().unwrap().probubility() - 0.2).abs() < 1e-12);
+		assert!((colors.filter(|c| *c == "red").probubility_or(&not_blue).unwrap() - 0.8).abs() < 1e-12);
+
+		let uniform: WeightedSet<u8, f64> = WeightedSet::new_from_array(&[1,2,3,4]);
+		assert_eq!(uniform.subset_from_range(0, 1).probubility(), 0.25);
+	}
+
+	#[test] fn test_weighted_invalid() {
+		assert!(WeightedSet::new_weighted(&[1,2], &[0.5]).is_none());
+		assert!(WeightedSet::new_weighted(&[1,1], &[0.5, 0.5]).is_none());
+		assert!(WeightedSet::new_weighted(&[1,2], &[1.5, -0.5]).is_none());
+		assert!(WeightedSet::new_weighted(&[1,2], &[f64::NAN, 0.5]).is_none());
+	}
+// endregion:
+}
@@ -0,0 +1,228 @@
+use crate::math::datatypes::rkgtab::RkgTabN;
+use crate::math::datatypes::set::{TSet, TSetElement, TWeight, VecSet, WeightedSet};
+use crate::math::statistics::probubility::ProbubilityError;
+
+// MARK: RandomVariable
+
+/// A function from the outcomes of a sample space to numbers
+///
+/// The function is evaluated once for every outcome when the variable is
+/// made, so the variable keeps each outcome with its probubility and value.
+/// Two variables can only be combined when they are over the same space.
+pub struct RandomVariable<T> {
+	outcomes: Vec<T>,
+	probubilities: Vec<f64>,
+	values: Vec<f64>,
+}
+
+impl<T: TSetElement> RandomVariable<T> {
+	/// returns a new variable over space where every outcome is equally likely
+	/// returns None if the space is empty
+	pub fn new(space: &VecSet<T>, f: impl Fn(&T) -> f64) -> Option<Self> {
+		if space.size() == 0 { return None }
+
+		let p = 1.0 / space.size() as f64;
+		Some(Self {
+			outcomes: space.iter().copied().collect(),
+			probubilities: vec![p; space.size()],
+			values: space.iter().map(f).collect(),
+		})
+	}
+
+	/// returns a new variable over space where each outcome is as likely as its weight
+	/// returns None if the total weight of the space is zero
+	pub fn from_weighted<W: TWeight>(space: &WeightedSet<T, W>, f: impl Fn(&T) -> f64) -> Option<Self> {
+		let total = space.mass();
+		if total == W::zero() { return None }
+
+		Some(Self {
+			outcomes: space.iter().map(|(x, _)| *x).collect(),
+			probubilities: space.iter().map(|(_, w)| w.ratio(&total)).collect(),
+			values: space.iter().map(|(x, _)| f(x)).collect(),
+		})
+	}
+
+	/// returns the value the variable takes for outcome, None if it is not in the space
+	pub fn value(&self, outcome: &T) -> Option<f64> {
+		self.outcomes.iter().position(|x| x == outcome).map(|i| self.values[i])
+	}
+
+	/// returns P(X = value)
+	pub fn probubility_of(&self, value: f64) -> f64 {
+		self.pairs().filter(|(x, _)| *x == value).map(|(_, p)| p).sum()
+	}
+
+	/// E[X], the probubility weighted mean of the values
+	pub fn expectation(&self) -> f64 {
+		self.pairs().map(|(x, p)| x * p).sum()
+	}
+
+	/// Var(X) = E[(X - E[X])^2]
+	pub fn variance(&self) -> f64 {
+		let mean = self.expectation();
+		self.pairs().map(|(x, p)| (x - mean).powi(2) * p).sum()
+	}
+
+	/// the square root of the variance
+	pub fn standard_deviation(&self) -> f64 {
+		self.variance().sqrt()
+	}
+
+	/// Cov(X, Y) = E[(X - E[X])(Y - E[Y])]
+	/// returns an error if the variables are not over the same space
+	pub fn covariance(&self, other: &Self) -> Result<f64, ProbubilityError> {
+		self.check_space(other)?;
+
+		let (mean_x, mean_y) = (self.expectation(), other.expectation());
+		Ok(self.pairs()
+			.zip(&other.values)
+			.map(|((x, p), y)| (x - mean_x) * (y - mean_y) * p)
+			.sum())
+	}
+
+	/// returns the variable g(X)
+	pub fn map(&self, g: impl Fn(f64) -> f64) -> Self {
+		Self {
+			outcomes: self.outcomes.clone(),
+			probubilities: self.probubilities.clone(),
+			values: self.values.iter().map(|x| g(*x)).collect(),
+		}
+	}
+
+	/// returns the variable g(X, Y), like X + Y with `|x, y| x + y`
+	/// returns an error if the variables are not over the same space
+	pub fn combine(&self, other: &Self, g: impl Fn(f64, f64) -> f64) -> Result<Self, ProbubilityError> {
+		self.check_space(other)?;
+
+		Ok(Self {
+			outcomes: self.outcomes.clone(),
+			probubilities: self.probubilities.clone(),
+			values: self.values.iter().zip(&other.values).map(|(x, y)| g(*x, *y)).collect(),
+		})
+	}
+
+	/// returns the distribution as a table with a row for every value the
+	/// variable takes, in increasing order, and collumns value and probubility
+	pub fn distribution(&self) -> RkgTabN<f64> {
+		let mut pairs: Vec<(f64, f64)> = self.pairs().collect();
+		pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+		let mut flat: Vec<f64> = Vec::new();
+		for (x, p) in pairs {
+			match flat.len() {
+				n if n > 0 && flat[n-2] == x => flat[n-1] += p,
+				_ => flat.extend([x, p]),
+			}
+		}
+
+		let mut table = RkgTabN::from_flat(&flat, &[flat.len() / 2, 2]);
+		table.set_labels_from_flat(&["value".to_string(), "probubility".to_string()], &[1]);
+		table
+	}
+
+	fn pairs(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+		self.values.iter().copied().zip(self.probubilities.iter().copied())
+	}
+
+	fn check_space(&self, other: &Self) -> Result<(), ProbubilityError> {
+		if self.outcomes != other.outcomes || self.probubilities != other.probubilities {
+			return Err(ProbubilityError::DifferentSampleSpace)
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::math::datatypes::rational::Rational;
+	use crate::math::datatypes::set::cartesian_pairs;
+
+	fn assert_close(actual: f64, expected: f64) {
+		assert!((actual - expected).abs() <= 1e-12, "expected {} but got {}", expected, actual);
+	}
+
+	fn two_dice() -> VecSet<(u32, u32)> {
+		let die = VecSet::new_from_array(&[1, 2, 3, 4, 5, 6]);
+		cartesian_pairs(&die, &die).collect()
+	}
+
+	// region: test_moments
+	#[test] fn test_single_die() {
+		let die = VecSet::new_from_array(&[1, 2, 3, 4, 5, 6]);
+		let x = RandomVariable::new(&die, |x| *x as f64).unwrap();
+		assert_close(x.expectation(), 3.5);
+		assert_close(x.variance(), 35.0 / 12.0);
+	}
+
+	#[test] fn test_sum_of_two_dice() {
+		let space = two_dice();
+		let sum = RandomVariable::new(&space, |(a, b)| (a + b) as f64).unwrap();
+		assert_close(sum.expectation(), 7.0);
+		assert_close(sum.variance(), 35.0 / 6.0);
+		assert_close(sum.probubility_of(7.0), 1.0 / 6.0);
+		assert_close(sum.probubility_of(13.0), 0.0);
+	}
+
+	#[test] fn test_weighted_space() {
+		let coin = WeightedSet::new_weighted(&[0, 1], &[Rational::new_from_fraction(1, 4), Rational::new_from_fraction(3, 4)]).unwrap();
+		let x = RandomVariable::from_weighted(&coin, |x| *x as f64).unwrap();
+		assert_close(x.expectation(), 0.75);
+		assert_close(x.variance(), 0.1875);
+	}
+
+	#[test] fn test_empty_space() {
+		assert!(RandomVariable::new(&VecSet::<u32>::new(), |x| *x as f64).is_none());
+		let space = WeightedSet::new_weighted(&[1, 2], &[0.0, 0.0]).unwrap();
+		assert!(RandomVariable::from_weighted(&space, |x| *x as f64).is_none());
+	}
+	// endregion:
+
+	// region: test_covariance
+	#[test] fn test_covariance() {
+		let space = two_dice();
+		let first = RandomVariable::new(&space, |(a, _)| *a as f64).unwrap();
+		let second = RandomVariable::new(&space, |(_, b)| *b as f64).unwrap();
+		let sum = first.combine(&second, |x, y| x + y).unwrap();
+
+		// independent dice do not vary together
+		assert_close(first.covariance(&second).unwrap(), 0.0);
+		assert_close(first.covariance(&sum).unwrap(), 35.0 / 12.0);
+		assert_close(sum.covariance(&sum).unwrap(), sum.variance());
+	}
+
+	#[test] fn test_different_space() {
+		let die = VecSet::new_from_array(&[1, 2, 3, 4, 5, 6]);
+		let x = RandomVariable::new(&die, |x| *x as f64).unwrap();
+		let coin = VecSet::new_from_array(&[1, 2]);
+		let y = RandomVariable::new(&coin, |x| *x as f64).unwrap();
+		assert_eq!(x.covariance(&y), Err(ProbubilityError::DifferentSampleSpace));
+		assert!(x.combine(&y, |a, b| a + b).is_err());
+	}
+	// endregion:
+
+	// region: test_distribution
+	#[test] fn test_distribution_table() {
+		let sum = RandomVariable::new(&two_dice(), |(a, b)| (a + b) as f64).unwrap();
+		let table = sum.distribution();
+
+		assert_eq!(table.shape(), vec![11, 2]);
+		assert_eq!(table.get_label_by_axis(1, 1), "probubility");
+		assert_eq!(table.get(&[0, 0]), 2.0);
+		assert_close(table.get(&[0, 1]), 1.0 / 36.0);
+		assert_eq!(table.get(&[5, 0]), 7.0);
+		assert_close(table.get(&[5, 1]), 6.0 / 36.0);
+		assert_eq!(table.get(&[10, 0]), 12.0);
+	}
+
+	#[test] fn test_map() {
+		let die = VecSet::new_from_array(&[1, 2, 3, 4, 5, 6]);
+		let x = RandomVariable::new(&die, |x| *x as f64).unwrap();
+		let even = x.map(|v| (v % 2.0 == 0.0) as u8 as f64);
+		assert_close(even.expectation(), 0.5);
+		assert_eq!(even.distribution().shape(), vec![2, 2]);
+		assert_eq!(x.value(&4), Some(4.0));
+		assert_eq!(x.value(&7), None);
+	}
+	// endregion:
+}
@@ -0,0 +1,243 @@
+use std::fmt::Display;
+
+use crate::math::datatypes::rkgtab::RkgTabN;
+
+// Fits take a 2d table of rows of observations and the indices of the
+// collumns to use, they return None when the collumns do not exist, there
+// are fewer rows than coefficients, or the x values can not separate the
+// coefficients (like a collumn that is a copy of another).
+
+// MARK: Regression
+
+// how the x values of a row turn into the terms multiplied by the coefficients
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Terms {
+	// one term for each x collumn
+	Linear(usize),
+	// powers 1 to degree of a single x
+	Polynomial(usize),
+}
+
+impl Terms {
+	fn expand(&self, x: &[f64]) -> Option<Vec<f64>> {
+		match *self {
+			Terms::Linear(count) => (x.len() == count).then(|| x.to_vec()),
+			Terms::Polynomial(degree) => {
+				if x.len() != 1 { return None }
+				Some((1..=degree).map(|power| x[0].powi(power as i32)).collect())
+			}
+		}
+	}
+}
+
+/// A least squares fit of y against one or more x values
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+	/// the intercept followed by one coefficient for each x collumn, or for
+	/// each power of x in a polynomial fit
+	pub coefficients: Vec<f64>,
+	/// the fraction of the variance of y explained by the fit, NaN if y is constant
+	pub r_squared: f64,
+	/// y minus the predicted y for each row
+	pub residuals: Vec<f64>,
+	terms: Terms,
+}
+
+impl Regression {
+	/// returns the predicted y for the x values of one row, in the order the
+	/// x collumns were given, or the single x of a polynomial fit
+	/// None if x has the wrong number of values
+	pub fn predict(&self, x: &[f64]) -> Option<f64> {
+		let terms = self.terms.expand(x)?;
+		Some(self.coefficients[0] + terms.iter().zip(&self.coefficients[1..]).map(|(t, c)| t * c).sum::<f64>())
+	}
+}
+
+// MARK: Fitting
+
+/// Simple linear regression, y = b0 + b1 x
+pub fn linear_regression<T>(table: &RkgTabN<T>, x_col: usize, y_col: usize) -> Option<Regression>
+where T: Clone + Copy + Display + Into<f64> {
+	multiple_regression(table, &[x_col], y_col)
+}
+
+/// Multiple linear regression, y = b0 + b1 x1 + b2 x2 + ...
+pub fn multiple_regression<T>(table: &RkgTabN<T>, x_cols: &[usize], y_col: usize) -> Option<Regression>
+where T: Clone + Copy + Display + Into<f64> {
+	let x = collumns(table, x_cols)?;
+	let y = collumns(table, &[y_col])?;
+	fit(&x, &y[0], Terms::Linear(x_cols.len()))
+}
+
+/// Polynomial regression, y = b0 + b1 x + b2 x^2 + ... + bd x^d
+/// None if degree is zero
+pub fn polynomial_regression<T>(table: &RkgTabN<T>, x_col: usize, y_col: usize, degree: usize) -> Option<Regression>
+where T: Clone + Copy + Display + Into<f64> {
+	if degree == 0 { return None }
+	let x = collumns(table, &[x_col])?;
+	let y = collumns(table, &[y_col])?;
+	fit(&x, &y[0], Terms::Polynomial(degree))
+}
+
+// the values of each collumn, None if the table is not 2d or a collumn is out of bounds
+fn collumns<T: Clone + Copy + Display + Into<f64>>(table: &RkgTabN<T>, cols: &[usize]) -> Option<Vec<Vec<f64>>> {
+	let shape = table.shape();
+	if shape.len() != 2 || cols.iter().any(|c| *c >= shape[1]) { return None }
+	Some(cols.iter().map(|c| (0..shape[0]).map(|i| table.get(&[i, *c]).into()).collect()).collect())
+}
+
+fn fit(x: &[Vec<f64>], y: &[f64], terms: Terms) -> Option<Regression> {
+	let rows = y.len();
+
+	// design matrix stored by collumn, starting with the intercept
+	let mut design = vec![vec![1.0; rows]];
+	for i in 0..rows {
+		let row: Vec<f64> = x.iter().map(|col| col[i]).collect();
+		for (j, term) in terms.expand(&row)?.into_iter().enumerate() {
+			if design.len() <= j + 1 { design.push(vec![0.0; rows]); }
+			design[j + 1][i] = term;
+		}
+	}
+	if rows < design.len() { return None }
+
+	let coefficients = least_squares(design, y.to_vec())?;
+	let mut regression = Regression { coefficients, r_squared: f64::NAN, residuals: Vec::with_capacity(rows), terms };
+
+	for (i, actual) in y.iter().enumerate() {
+		let row: Vec<f64> = x.iter().map(|col| col[i]).collect();
+		regression.residuals.push(actual - regression.predict(&row)?);
+	}
+
+	let mean = y.iter().sum::<f64>() / rows as f64;
+	let total: f64 = y.iter().map(|v| (v - mean).powi(2)).sum();
+	let unexplained: f64 = regression.residuals.iter().map(|r| r * r).sum();
+	if total > 0.0 { regression.r_squared = 1.0 - unexplained / total; }
+
+	Some(regression)
+}
+
+// Solves for the b minimising |a b - y| with Householder QR, which stays
+// accurate for the badly conditioned collumns of high degree polynomials.
+// a is given by collumn, None if the collumns are linearly dependent
+fn least_squares(mut a: Vec<Vec<f64>>, mut y: Vec<f64>) -> Option<Vec<f64>> {
+	let rows = y.len();
+	let cols = a.len();
+	let scale = a.iter().map(|col| norm(col)).fold(0.0, f64::max);
+
+	let mut diagonal = vec![0.0; cols];
+	for k in 0..cols {
+		let length = norm(&a[k][k..]);
+		if length <= 1e-12 * scale { return None }
+
+		// reflect a[k][k..] onto the k-th axis
+		let alpha = if a[k][k] > 0.0 { -length } else { length };
+		let mut v = a[k][k..].to_vec();
+		v[0] -= alpha;
+		let v_squared: f64 = v.iter().map(|x| x * x).sum();
+		diagonal[k] = alpha;
+
+		let reflect = |col: &mut [f64]| {
+			let s = 2.0 * v.iter().zip(col.iter()).map(|(a, b)| a * b).sum::<f64>() / v_squared;
+			col.iter_mut().zip(&v).for_each(|(c, v)| *c -= s * v);
+		};
+		for col in a[k + 1..].iter_mut() { reflect(&mut col[k..]); }
+		reflect(&mut y[k..rows]);
+	}
+
+	// back substitution through the upper triangle
+	let mut b = vec![0.0; cols];
+	for k in (0..cols).rev() {
+		let sum: f64 = (k + 1..cols).map(|j| a[j][k] * b[j]).sum();
+		b[k] = (y[k] - sum) / diagonal[k];
+	}
+
+	Some(b)
+}
+
+fn norm(values: &[f64]) -> f64 {
+	values.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+		assert!((actual - expected).abs() <= tolerance, "expected {} but got {}", expected, actual);
+	}
+
+	// region: test_linear
+	#[test] fn test_exact_line() {
+		let table = RkgTabN::from_flat(&[0, 1, 1, 3, 2, 5, 3, 7], &[4, 2]);
+		let fit = linear_regression(&table, 0, 1).unwrap();
+		assert_close(fit.coefficients[0], 1.0, 1e-12);
+		assert_close(fit.coefficients[1], 2.0, 1e-12);
+		assert_close(fit.r_squared, 1.0, 1e-12);
+		assert!(fit.residuals.iter().all(|r| r.abs() < 1e-12));
+		assert_close(fit.predict(&[10.0]).unwrap(), 21.0, 1e-12);
+		assert!(fit.predict(&[1.0, 2.0]).is_none());
+	}
+
+	#[test] fn test_noisy_line() {
+		let table = RkgTabN::from_flat(&[1.0, 2.0, 2.0, 4.0, 3.0, 5.0, 4.0, 4.0, 5.0, 5.0], &[5, 2]);
+		let fit = linear_regression(&table, 0, 1).unwrap();
+		assert_close(fit.coefficients[0], 2.2, 1e-12);
+		assert_close(fit.coefficients[1], 0.6, 1e-12);
+		assert_close(fit.r_squared, 0.6, 1e-12);
+		assert_close(fit.residuals[0], -0.8, 1e-12);
+		assert_close(fit.residuals.iter().sum::<f64>(), 0.0, 1e-12);
+	}
+
+	#[test] fn test_multiple() {
+		// y = 1 + 2 x1 - 3 x2, the y collumn is first
+		let mut data = Vec::new();
+		for (x1, x2) in [(0.0, 1.0), (1.0, 0.0), (2.0, 3.0), (3.0, 1.0), (4.0, 5.0)] {
+			data.extend([1.0 + 2.0 * x1 - 3.0 * x2, x1, x2]);
+		}
+		let table = RkgTabN::from_flat(&data, &[5, 3]);
+		let fit = multiple_regression(&table, &[1, 2], 0).unwrap();
+		assert_close(fit.coefficients[0], 1.0, 1e-12);
+		assert_close(fit.coefficients[1], 2.0, 1e-12);
+		assert_close(fit.coefficients[2], -3.0, 1e-12);
+		assert_close(fit.predict(&[1.0, 1.0]).unwrap(), 0.0, 1e-12);
+	}
+	// endregion:
+
+	// region: test_polynomial
+	#[test] fn test_polynomial() {
+		// y = 2 - x + 0.5 x^2
+		let data: Vec<f64> = (0..6).flat_map(|x| { let x = x as f64; [x, 2.0 - x + 0.5 * x * x] }).collect();
+		let table = RkgTabN::from_flat(&data, &[6, 2]);
+		let fit = polynomial_regression(&table, 0, 1, 2).unwrap();
+		assert_close(fit.coefficients[0], 2.0, 1e-10);
+		assert_close(fit.coefficients[1], -1.0, 1e-10);
+		assert_close(fit.coefficients[2], 0.5, 1e-10);
+		assert_close(fit.predict(&[10.0]).unwrap(), 42.0, 1e-8);
+
+		// a line fits the curve less well
+		let line = polynomial_regression(&table, 0, 1, 1).unwrap();
+		assert!(line.r_squared < fit.r_squared);
+		assert!(polynomial_regression(&table, 0, 1, 0).is_none());
+	}
+	// endregion:
+
+	// region: test_invalid
+	#[test] fn test_invalid() {
+		let table = RkgTabN::from_flat(&[1, 2, 3, 4], &[2, 2]);
+		assert!(linear_regression(&table, 0, 2).is_none());
+		assert!(polynomial_regression(&table, 0, 1, 2).is_none());
+		assert!(linear_regression(&RkgTabN::from_flat(&[1, 2, 3], &[3]), 0, 0).is_none());
+
+		// x is the same in every row so the slope can not be found
+		let flat = RkgTabN::from_flat(&[1, 2, 1, 3, 1, 4], &[3, 2]);
+		assert!(linear_regression(&flat, 0, 1).is_none());
+
+		// a collumn that copies another
+		let copy = RkgTabN::from_flat(&[1, 1, 5, 2, 2, 7, 3, 3, 8], &[3, 3]);
+		assert!(multiple_regression(&copy, &[0, 1], 2).is_none());
+
+		let constant = linear_regression(&RkgTabN::from_flat(&[1, 2, 2, 2, 3, 2], &[3, 2]), 0, 1).unwrap();
+		assert!(constant.r_squared.is_nan());
+	}
+	// endregion:
+}
@@ -0,0 +1,220 @@
+use std::fmt::Display;
+
+use rand::rngs::StdRng;
+use rand::seq::{SliceRandom, index};
+use rand::{Rng, SeedableRng};
+
+use crate::math::datatypes::rkgtab::RkgTabN;
+use crate::math::datatypes::set::{TSet, TSetElement, TWeight, VecSet, WeightedSet};
+
+// MARK: Sampler
+
+/// A handle to a random number generator for drawing samples
+///
+/// Two samplers made from the same seed give the same samples in the same
+/// order, so tests and simulations can be repeated.  The generator can also
+/// be borrowed with `rng` to sample the distributions.
+pub struct Sampler {
+	rng: StdRng,
+}
+
+impl Sampler {
+	/// returns a sampler that always gives the same samples for the same seed
+	pub fn new(seed: u64) -> Self {
+		Self { rng: StdRng::seed_from_u64(seed) }
+	}
+
+	/// returns a sampler seeded by the operating system, for when the samples
+	/// do not need to be repeated
+	pub fn from_os_rng() -> Self {
+		Self { rng: StdRng::from_os_rng() }
+	}
+
+	/// returns the generator to draw values with directly
+	pub fn rng(&mut self) -> &mut StdRng {
+		&mut self.rng
+	}
+
+	/// returns n elements drawn from set, each draw can pick any element
+	/// None if set is empty
+	pub fn sample_with_replacement<T: TSetElement>(&mut self, set: &VecSet<T>, n: usize) -> Option<Vec<T>> {
+		if set.size() == 0 { return None }
+		Some((0..n).map(|_| set[self.rng.random_range(0..set.size())]).collect())
+	}
+
+	/// returns a subset of n different elements of set, in the order they
+	/// have in set
+	/// None if n is more than the size of set
+	pub fn sample_without_replacement<T: TSetElement>(&mut self, set: &VecSet<T>, n: usize) -> Option<VecSet<T>> {
+		if n > set.size() { return None }
+
+		let mut indices = index::sample(&mut self.rng, set.size(), n).into_vec();
+		indices.sort_unstable();
+		Some(set.subset_from_indices(&indices))
+	}
+
+	/// returns n elements drawn from set, each as likely as its share of the
+	/// total weight, each draw can pick any element
+	/// None if set has no weight
+	pub fn weighted_sample<T: TSetElement, W: TWeight>(&mut self, set: &WeightedSet<T, W>, n: usize) -> Option<Vec<T>> {
+		let total = set.mass();
+		if total == W::zero() { return None }
+
+		// running total of the probubilities, set to one from the last element
+		// with weight so rounding can never leave a draw past it
+		let mut cumulative: Vec<(T, f64)> = Vec::with_capacity(set.size());
+		let mut sum = 0.0;
+		let mut last = 0;
+		for (i, (x, w)) in set.iter().enumerate() {
+			sum += w.ratio(&total);
+			cumulative.push((*x, sum));
+			if *w != W::zero() { last = i; }
+		}
+		cumulative[last..].iter_mut().for_each(|c| c.1 = 1.0);
+
+		let draws = (0..n).map(|_| {
+			let u = self.rng.random::<f64>();
+			// elements with no weight have the same total as the one before and are never picked
+			cumulative[cumulative.partition_point(|(_, c)| *c <= u)].0
+		});
+		Some(draws.collect())
+	}
+
+	/// puts items in a random order
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		items.shuffle(&mut self.rng);
+	}
+
+	/// returns a table of as many rows as table, each drawn from its rows
+	/// with replacement
+	/// None if table has no rows
+	pub fn bootstrap<T: Clone + Copy + Display>(&mut self, table: &RkgTabN<T>) -> Option<RkgTabN<T>> {
+		let rows = table.shape()[0];
+		if rows == 0 { return None }
+
+		let picked: Vec<usize> = (0..rows).map(|_| self.rng.random_range(0..rows)).collect();
+		Some(table.select_rows(&picked))
+	}
+
+	/// splits the rows of table into a training table and a testing table
+	/// with test_fraction of the rows, rounded, both keep the order of table
+	/// None if test_fraction is not in [0, 1]
+	pub fn train_test_split<T: Clone + Copy + Display>(
+		&mut self,
+		table: &RkgTabN<T>,
+		test_fraction: f64
+	) -> Option<(RkgTabN<T>, RkgTabN<T>)> {
+		if !(0.0..=1.0).contains(&test_fraction) { return None }
+
+		let rows = table.shape()[0];
+		let test_rows = (rows as f64 * test_fraction).round() as usize;
+		let mut test = index::sample(&mut self.rng, rows, test_rows).into_vec();
+		test.sort_unstable();
+
+		let train: Vec<usize> = (0..rows).filter(|r| test.binary_search(r).is_err()).collect();
+		Some((table.select_rows(&train), table.select_rows(&test)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::math::statistics::distributions::{Normal, TContinuousDistribution};
+	use crate::math::statistics::probubility::TProbubility;
+
+	// region: test_sets
+	#[test] fn test_same_seed_same_samples() {
+		let set = VecSet::new_from_array(&[1, 2, 3, 4, 5, 6]);
+		let (mut a, mut b) = (Sampler::new(42), Sampler::new(42));
+		assert_eq!(a.sample_with_replacement(&set, 20), b.sample_with_replacement(&set, 20));
+		assert_eq!(Normal::standard().sample(a.rng()), Normal::standard().sample(b.rng()));
+
+		let mut c = Sampler::new(43);
+		assert_ne!(a.sample_with_replacement(&set, 20), c.sample_with_replacement(&set, 20));
+	}
+
+	#[test] fn test_with_replacement() {
+		let set = VecSet::new_from_array(&[1, 2, 3]);
+		let mut sampler = Sampler::new(1);
+		let draws = sampler.sample_with_replacement(&set, 100).unwrap();
+		assert_eq!(draws.len(), 100);
+		assert!(draws.iter().all(|x| set.contains(x)));
+		assert!(set.iter().all(|x| draws.contains(x)));
+		assert!(sampler.sample_with_replacement(&VecSet::<u32>::new(), 1).is_none());
+	}
+
+	#[test] fn test_without_replacement() {
+		let set = VecSet::new_from_array(&[10, 20, 30, 40, 50]);
+		let mut sampler = Sampler::new(2);
+		let subset = sampler.sample_without_replacement(&set, 3).unwrap();
+		assert_eq!(subset.size(), 3);
+		assert!(subset.is_subset_of(&set));
+		assert!(subset.iter().zip(subset.iter().skip(1)).all(|(a, b)| a < b));
+		// the sample is an event in the space of set
+		assert_eq!(subset.probubility(), 0.6);
+
+		assert_eq!(sampler.sample_without_replacement(&set, 5).unwrap().size(), 5);
+		assert!(sampler.sample_without_replacement(&set, 6).is_none());
+	}
+
+	#[test] fn test_weighted() {
+		let set = WeightedSet::new_weighted(&['a', 'b', 'c', 'd'], &[1.0, 0.0, 3.0, 0.0]).unwrap();
+		let mut sampler = Sampler::new(3);
+		let draws = sampler.weighted_sample(&set, 20000).unwrap();
+		assert!(!draws.contains(&'b') && !draws.contains(&'d'));
+		let share = draws.iter().filter(|x| **x == 'c').count() as f64 / 20000.0;
+		assert!((share - 0.75).abs() < 0.02, "share of c was {}", share);
+
+		let empty = WeightedSet::new_weighted(&['a'], &[0.0]).unwrap();
+		assert!(sampler.weighted_sample(&empty, 1).is_none());
+	}
+
+	#[test] fn test_shuffle() {
+		let mut items: Vec<u32> = (0..20).collect();
+		Sampler::new(4).shuffle(&mut items);
+		assert_ne!(items, (0..20).collect::<Vec<u32>>());
+
+		let mut again: Vec<u32> = (0..20).collect();
+		Sampler::new(4).shuffle(&mut again);
+		assert_eq!(items, again);
+
+		items.sort();
+		assert_eq!(items, (0..20).collect::<Vec<u32>>());
+	}
+	// endregion:
+
+	// region: test_tables
+	fn table() -> RkgTabN<i32> {
+		let data: Vec<i32> = (0..10).flat_map(|i| [i, i * 10]).collect();
+		let mut table = RkgTabN::from_flat(&data, &[10, 2]);
+		table.set_labels_from_flat(&["x".to_string(), "y".to_string()], &[1]);
+		table
+	}
+
+	#[test] fn test_bootstrap() {
+		let table = table();
+		let resampled = Sampler::new(5).bootstrap(&table).unwrap();
+		assert_eq!(resampled.shape(), vec![10, 2]);
+		assert_eq!(resampled.get_label_by_axis(1, 1), "y");
+		// rows are kept whole
+		assert!((0..10).all(|i| resampled.get(&[i, 1]) == resampled.get(&[i, 0]) * 10));
+
+		let again = Sampler::new(5).bootstrap(&table).unwrap();
+		assert!((0..10).all(|i| resampled.get(&[i, 0]) == again.get(&[i, 0])));
+		assert!(Sampler::new(5).bootstrap(&RkgTabN::<i32>::from_flat(&[], &[0, 2])).is_none());
+	}
+
+	#[test] fn test_train_test_split() {
+		let (train, test) = Sampler::new(6).train_test_split(&table(), 0.3).unwrap();
+		assert_eq!((train.shape(), test.shape()), (vec![7, 2], vec![3, 2]));
+		assert_eq!(test.get_label_by_axis(1, 0), "x");
+
+		// every row ends up in exactly one side
+		let mut rows: Vec<i32> = (0..7).map(|i| train.get(&[i, 0])).chain((0..3).map(|i| test.get(&[i, 0]))).collect();
+		rows.sort();
+		assert_eq!(rows, (0..10).collect::<Vec<i32>>());
+
+		assert!(Sampler::new(6).train_test_split(&table(), 1.5).is_none());
+	}
+	// endregion:
+}